@@ -0,0 +1,102 @@
+use crate::interval::Interval;
+use crate::vec3::Point3;
+use crate::ray::Ray;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+	pub x: Interval,
+	pub y: Interval,
+	pub z: Interval
+}
+
+impl Aabb {
+	pub fn new(x: Interval, y: Interval, z: Interval) -> Aabb {
+		Aabb {
+			x,
+			y,
+			z
+		}
+	}
+
+	pub fn empty() -> Aabb {
+		Aabb::new(Interval::empty(), Interval::empty(), Interval::empty())
+	}
+
+	pub fn universe() -> Aabb {
+		Aabb::new(Interval::universe(), Interval::universe(), Interval::universe())
+	}
+
+	pub fn from_points(a: Point3, b: Point3) -> Aabb {
+		Aabb {
+			x: Interval::new(f64::min(a.x(), b.x()), f64::max(a.x(), b.x())),
+			y: Interval::new(f64::min(a.y(), b.y()), f64::max(a.y(), b.y())),
+			z: Interval::new(f64::min(a.z(), b.z()), f64::max(a.z(), b.z()))
+		}
+	}
+
+	pub fn surrounding(a: &Aabb, b: &Aabb) -> Aabb {
+		Aabb {
+			x: Interval::union(&a.x, &b.x),
+			y: Interval::union(&a.y, &b.y),
+			z: Interval::union(&a.z, &b.z)
+		}
+	}
+
+	pub fn axis(&self, n: usize) -> Interval {
+		match n {
+			1 => self.y,
+			2 => self.z,
+			_ => self.x
+		}
+	}
+
+	pub fn min(&self) -> Point3 {
+		Point3::new(self.x.min, self.y.min, self.z.min)
+	}
+
+	pub fn max(&self) -> Point3 {
+		Point3::new(self.x.max, self.y.max, self.z.max)
+	}
+
+	// Flat shapes (quads, disks) would be missed by the slab test, so every axis gets a minimal thickness
+	pub fn pad(&self) -> Aabb {
+		let delta = 0.0001;
+
+		let x = if self.x.size() >= delta { self.x } else { self.x.expand(delta) };
+		let y = if self.y.size() >= delta { self.y } else { self.y.expand(delta) };
+		let z = if self.z.size() >= delta { self.z } else { self.z.expand(delta) };
+
+		Aabb::new(x, y, z)
+	}
+
+	pub fn hit(&self, ray: &Ray, t: &Interval) -> bool {
+		self.clip(ray, t).is_some()
+	}
+
+	// Part of `t` where the ray is inside the box
+	pub fn clip(&self, ray: &Ray, t: &Interval) -> Option<Interval> {
+		let origin = ray.origin();
+		let direction = ray.direction();
+
+		let mut t_min = t.min;
+		let mut t_max = t.max;
+		for axis in 0..3 {
+			let interval = self.axis(axis);
+			let inv_d = 1. / direction.axis(axis);
+
+			let mut t0 = (interval.min - origin.axis(axis)) * inv_d;
+			let mut t1 = (interval.max - origin.axis(axis)) * inv_d;
+			if inv_d < 0. {
+				std::mem::swap(&mut t0, &mut t1);
+			}
+
+			t_min = f64::max(t0, t_min);
+			t_max = f64::min(t1, t_max);
+			if t_max <= t_min {
+				return None;
+			}
+		}
+
+		Some(Interval::new(t_min, t_max))
+	}
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::interval::Interval;
+use crate::texture::Texture;
+use crate::aabb::Aabb;
+use crate::ray::Ray;
+use crate::vec3::{Vec3, Point3};
+
+use rand::Rng;
+
+// Most cut out surfaces skipped along one ray, e.g. the front and back of a leaf
+const MAX_LAYERS: usize = 64;
+
+enum AlphaTest {
+	Threshold(f64),
+	Stochastic
+}
+
+// Cuts parts of an object away where the first channel of `alpha` is low, hits there are skipped and the
+// search goes on behind them so the rest of the scene shows through
+pub struct AlphaMask {
+	object: Arc<dyn Hittable>,
+	alpha: Arc<dyn Texture>,
+	test: AlphaTest
+}
+
+impl Hittable for AlphaMask {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let mut t_min = t.min;
+		for _ in 0..MAX_LAYERS {
+			let record = self.object.hit(ray, &Interval::new(t_min, t.max))?;
+			if self.opaque(&record) {
+				return Some(record);
+			}
+
+			t_min = record.t + f64::max(record.t.abs() * 1e-9, 1e-9);
+		}
+
+		None
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.object.bounding_box()
+	}
+
+	// Light sampling draws over the whole object, cut out parts included, so the density stays the one of
+	// `random`. Alpha is accounted for by the shadow ray, which goes through a cut out part and finds no
+	// light there. Skipping those samples would need the opaque fraction to renormalize the density
+	fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+		self.object.pdf_value(origin, direction)
+	}
+
+	fn random(&self, origin: &Point3) -> Vec3 {
+		self.object.random(origin)
+	}
+}
+
+impl AlphaMask {
+	// Surfaces with an alpha below 0.5 are cut out
+	pub fn new(object: Arc<dyn Hittable>, alpha: Arc<dyn Texture>) -> AlphaMask {
+		AlphaMask {
+			object,
+			alpha,
+			test: AlphaTest::Threshold(0.5)
+		}
+	}
+
+	pub fn threshold(mut self, threshold: f64) -> AlphaMask {
+		self.test = AlphaTest::Threshold(threshold);
+		self
+	}
+
+	// Each hit is kept with a probability equal to its alpha, averaging to partial transparency
+	pub fn stochastic(mut self) -> AlphaMask {
+		self.test = AlphaTest::Stochastic;
+		self
+	}
+
+	fn opaque(&self, record: &HitRecord) -> bool {
+		let alpha = self.alpha.value(record.u, record.v, &record.point).x();
+
+		match self.test {
+			AlphaTest::Threshold(threshold) => alpha >= threshold,
+			AlphaTest::Stochastic => rand::thread_rng().gen::<f64>() < alpha
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::alpha_mask::AlphaMask;
+	use crate::checker_texture::CheckerTexture;
+	use crate::solid_color::SolidColor;
+	use crate::hittable::Hittable;
+	use crate::hittable_list::HittableList;
+	use crate::cuboid::Cuboid;
+	use crate::quad::Quad;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::material::Material;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn material() -> Arc<dyn Material> {
+		Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+	}
+
+	// Unit square in z = 0.5, cut out for x < 0
+	fn leaf() -> Arc<Quad> {
+		Arc::new(Quad::new(Point3::new(-1., 0., 0.5), Vec3::new(2., 0., 0.), Vec3::new(0., 1., 0.), material()))
+	}
+
+	fn half_alpha() -> Arc<CheckerTexture> {
+		Arc::new(CheckerTexture::from_colors(1., Color::new(1., 1., 1.), Color::new(0., 0., 0.)))
+	}
+
+	#[test]
+	fn cutout() {
+		let mut world = HittableList::new();
+		world.push(Box::new(AlphaMask::new(leaf(), half_alpha())));
+		world.push(Box::new(Quad::new(Point3::new(-5., -5., -1.), Vec3::new(10., 0., 0.), Vec3::new(0., 10., 0.), material())));
+		let t = Interval::new(0.001, f64::MAX);
+
+		let opaque = world.hit(&Ray::new(Point3::new(0.5, 0.5, 5.), Vec3::new(0., 0., -1.)), &t).unwrap();
+		assert!(f64::abs(opaque.t - 4.5) < 1e-9);
+
+		// Through the cut out texel to the wall behind
+		let behind = world.hit(&Ray::new(Point3::new(-0.5, 0.5, 5.), Vec3::new(0., 0., -1.)), &t).unwrap();
+		assert!(f64::abs(behind.t - 6.) < 1e-9);
+	}
+
+	#[test]
+	fn every_layer_is_skipped() {
+		let transparent = Arc::new(SolidColor::new(Color::new(0., 0., 0.)));
+		let cuboid = Arc::new(Cuboid::new(Point3::new(0., 0., 0.), Point3::new(1., 1., 1.), material()));
+		let mask = AlphaMask::new(cuboid, transparent);
+
+		assert!(mask.hit(&Ray::new(Point3::new(0.5, 0.5, 5.), Vec3::new(0., 0., -1.)), &Interval::new(0.001, f64::MAX)).is_none());
+	}
+
+	#[test]
+	fn stochastic() {
+		let quarter = Arc::new(SolidColor::new(Color::new(0.25, 0.25, 0.25)));
+		let mask = AlphaMask::new(leaf(), quarter).stochastic();
+		let ray = Ray::new(Point3::new(0.5, 0.5, 5.), Vec3::new(0., 0., -1.));
+
+		let hits = (0..10000).filter(|_| mask.hit(&ray, &Interval::new(0.001, f64::MAX)).is_some()).count();
+		assert!((2200..2800).contains(&hits), "{}", hits);
+	}
+
+	#[test]
+	fn light_sampling() {
+		let leaf = leaf();
+		let mask = AlphaMask::new(leaf.clone(), half_alpha());
+		let origin = Point3::new(0., 0.5, 5.);
+
+		// Same density as the uncut object, cut out directions included
+		for direction in [Vec3::new(-0.5, 0., -4.5), Vec3::new(0.5, 0., -4.5)] {
+			assert_eq!(mask.pdf_value(&origin, &direction), leaf.pdf_value(&origin, &direction));
+		}
+		for _ in 0..100 {
+			let direction = mask.random(&origin);
+			assert!(mask.pdf_value(&origin, &direction) > 0.);
+		}
+	}
+}
\ No newline at end of file
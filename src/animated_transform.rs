@@ -0,0 +1,433 @@
+use crate::transform::{Transform, Matrix3};
+use crate::track::{Track, Keyable};
+use crate::vec3::Vec3;
+use crate::aabb::Aabb;
+
+const POLAR_ITERATIONS: usize = 100;
+const BOUND_STEPS: usize = 64;
+
+// Transform changing over time (Shoemake and Duff 1992). Keys are split into translation, rotation and scale,
+// which are interpolated separately so that spinning objects keep their shape instead of shrinking through
+// the linear blend of two rotation matrices
+#[derive(Clone, Debug)]
+pub struct AnimatedTransform {
+	start: Transform,
+	motion: Motion,
+	// Start and end times of a linear motion
+	linear_times: (f64, f64)
+}
+
+#[derive(Clone, Debug)]
+enum Motion {
+	Fixed,
+	Linear(Box<(Components, Components)>),
+	Keyframed(Track<Components>)
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Components {
+	translation: Vec3,
+	rotation: Quaternion,
+	scale: Matrix3
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Quaternion {
+	w: f64,
+	x: f64,
+	y: f64,
+	z: f64
+}
+
+impl Keyable for Components {
+	// Rotations are blended as quaternions put in the same hemisphere and renormalised
+	fn blend(values: &[Components; 4], weights: &[f64; 4]) -> Components {
+		let mut translation = Vec3::new(0., 0., 0.);
+		let mut rotation = Quaternion { w: 0., x: 0., y: 0., z: 0. };
+		let mut scale = [[0.; 3]; 3];
+		for (value, weight) in values.iter().zip(weights) {
+			translation += *weight * value.translation;
+
+			let aligned = if value.rotation.dot(&values[0].rotation) < 0. { value.rotation.negate() } else { value.rotation };
+			rotation = rotation.add(&aligned.scale(*weight));
+
+			for (row, value_row) in scale.iter_mut().zip(&value.scale) {
+				for (entry, value) in row.iter_mut().zip(value_row) {
+					*entry += weight * value;
+				}
+			}
+		}
+
+		Components {
+			translation,
+			rotation: rotation.scale(1. / rotation.dot(&rotation).sqrt()),
+			scale
+		}
+	}
+}
+
+impl AnimatedTransform {
+	// Moves from `start` at time 0 to `end` at time 1. Times are in seconds, as the frames of
+	// `Camera::render_frames`
+	pub fn new(start: Transform, end: Transform) -> AnimatedTransform {
+		let mut start_components = Components::decompose(&start);
+		let end_components = Components::decompose(&end);
+
+		// Rotations take the shortest way round
+		if start_components.rotation.dot(&end_components.rotation) < 0. {
+			start_components.rotation = start_components.rotation.negate();
+		}
+
+		AnimatedTransform {
+			start,
+			motion: Motion::Linear(Box::new((start_components, end_components))),
+			linear_times: (0., 1.)
+		}
+	}
+
+	// Moves from `start_time` to `end_time` instead, transforms that aren't linear motions are left alone
+	pub fn during(mut self, start_time: f64, end_time: f64) -> AnimatedTransform {
+		self.linear_times = (start_time, end_time);
+		self
+	}
+
+	pub fn fixed(transform: Transform) -> AnimatedTransform {
+		AnimatedTransform {
+			start: transform,
+			motion: Motion::Fixed,
+			linear_times: (0., 1.)
+		}
+	}
+
+	// Follows the keys of a track, e.g. a wheel turning over a whole shot
+	pub fn keyframed(track: &Track<Transform>) -> AnimatedTransform {
+		let start = track.values().next().map_or_else(Transform::identity, |(_, transform)| transform);
+
+		AnimatedTransform {
+			start,
+			motion: Motion::Keyframed(track.map(Components::decompose)),
+			linear_times: (0., 1.)
+		}
+	}
+
+	// Times outside of the keys hold the transform of the closest end
+	pub fn at(&self, time: f64) -> Transform {
+		self.components(time).map_or(self.start, |components| components.transform())
+	}
+
+	// Box swept by `bbox` over the whole motion. Corners are tracked at regular times, then the box is grown by
+	// the largest gap between a rotation arc and its chords
+	pub fn bounding_box(&self, bbox: &Aabb) -> Aabb {
+		let ((start_time, end_time), segments) = match &self.motion {
+			Motion::Fixed => return self.start.bounding_box(bbox),
+			Motion::Linear(..) => (self.linear_times, 1),
+			Motion::Keyframed(track) => (track.range(), usize::max(track.values().count(), 2) - 1)
+		};
+
+		let steps = BOUND_STEPS * segments;
+		let mut result = Aabb::empty();
+		let mut radius: f64 = 0.;
+		let mut angle: f64 = 0.;
+		let mut previous: Option<Quaternion> = None;
+		for step in 0..=steps {
+			let time = start_time + (end_time - start_time) * step as f64 / steps as f64;
+			let Some(components) = self.components(time) else {
+				continue;
+			};
+			let transform = components.transform();
+			let swept = transform.bounding_box(bbox);
+
+			for corner in [swept.min(), swept.max()] {
+				radius = f64::max(radius, (corner - components.translation).length());
+			}
+			if let Some(previous) = previous {
+				angle = f64::max(angle, 2. * f64::acos(f64::min(previous.dot(&components.rotation).abs(), 1.)));
+			}
+			previous = Some(components.rotation);
+
+			result = Aabb::surrounding(&result, &swept);
+		}
+
+		let padding = radius * (1. - f64::cos(angle / 2.));
+		let margin = Vec3::new(padding, padding, padding);
+
+		Aabb::surrounding(&result, &Aabb::from_points(result.min() - margin, result.max() + margin))
+	}
+
+	fn components(&self, time: f64) -> Option<Components> {
+		match &self.motion {
+			Motion::Fixed => None,
+			Motion::Linear(ends) => {
+				let (start, end) = ends.as_ref();
+				let (start_time, end_time) = self.linear_times;
+				let t = if end_time > start_time { (time - start_time) / (end_time - start_time) } else if time < start_time { 0. } else { 1. };
+				let t = f64::clamp(t, 0., 1.);
+				let mut scale = [[0.; 3]; 3];
+				for (i, row) in scale.iter_mut().enumerate() {
+					for (j, value) in row.iter_mut().enumerate() {
+						*value = (1. - t) * start.scale[i][j] + t * end.scale[i][j];
+					}
+				}
+
+				Some(Components {
+					translation: (1. - t) * start.translation + t * end.translation,
+					rotation: start.rotation.slerp(&end.rotation, t),
+					scale
+				})
+			},
+			Motion::Keyframed(track) => track.at(time)
+		}
+	}
+}
+
+impl Components {
+	// Polar decomposition M = R S of the linear part, iterating R towards the average of itself and its
+	// inverse transpose. Mirroring transforms keep the reflection in S so that R stays a proper rotation
+	fn decompose(transform: &Transform) -> Components {
+		let m = transform.linear_part();
+
+		let mut r = m;
+		for _ in 0..POLAR_ITERATIONS {
+			let inverse = Transform::linear(r).inverse().linear_part();
+
+			let mut next = [[0.; 3]; 3];
+			let mut change: f64 = 0.;
+			for i in 0..3 {
+				for j in 0..3 {
+					next[i][j] = 0.5 * (r[i][j] + inverse[j][i]);
+					change = f64::max(change, (next[i][j] - r[i][j]).abs());
+				}
+			}
+
+			r = next;
+			if change < 1e-12 {
+				break;
+			}
+		}
+
+		if determinant(&r) < 0. {
+			r = r.map(|row| row.map(|value| -value));
+		}
+
+		Components {
+			translation: transform.translation(),
+			rotation: Quaternion::from_matrix(&r),
+			scale: multiply(&transpose(&r), &m)
+		}
+	}
+
+	fn transform(&self) -> Transform {
+		Transform::translate(self.translation) * Transform::linear(multiply(&self.rotation.matrix(), &self.scale))
+	}
+}
+
+impl Quaternion {
+	// Shepperd's method, branching on the largest diagonal term for stability
+	fn from_matrix(m: &Matrix3) -> Quaternion {
+		let trace = m[0][0] + m[1][1] + m[2][2];
+		if trace > 0. {
+			let s = 2. * f64::sqrt(trace + 1.);
+			return Quaternion {
+				w: 0.25 * s,
+				x: (m[2][1] - m[1][2]) / s,
+				y: (m[0][2] - m[2][0]) / s,
+				z: (m[1][0] - m[0][1]) / s
+			};
+		}
+
+		if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+			let s = 2. * f64::sqrt(1. + m[0][0] - m[1][1] - m[2][2]);
+			Quaternion {
+				w: (m[2][1] - m[1][2]) / s,
+				x: 0.25 * s,
+				y: (m[0][1] + m[1][0]) / s,
+				z: (m[0][2] + m[2][0]) / s
+			}
+		}
+		else if m[1][1] > m[2][2] {
+			let s = 2. * f64::sqrt(1. + m[1][1] - m[0][0] - m[2][2]);
+			Quaternion {
+				w: (m[0][2] - m[2][0]) / s,
+				x: (m[0][1] + m[1][0]) / s,
+				y: 0.25 * s,
+				z: (m[1][2] + m[2][1]) / s
+			}
+		}
+		else {
+			let s = 2. * f64::sqrt(1. + m[2][2] - m[0][0] - m[1][1]);
+			Quaternion {
+				w: (m[1][0] - m[0][1]) / s,
+				x: (m[0][2] + m[2][0]) / s,
+				y: (m[1][2] + m[2][1]) / s,
+				z: 0.25 * s
+			}
+		}
+	}
+
+	fn matrix(&self) -> Matrix3 {
+		let Quaternion { w, x, y, z } = *self;
+
+		[
+			[1. - 2. * (y * y + z * z), 2. * (x * y - z * w), 2. * (x * z + y * w)],
+			[2. * (x * y + z * w), 1. - 2. * (x * x + z * z), 2. * (y * z - x * w)],
+			[2. * (x * z - y * w), 2. * (y * z + x * w), 1. - 2. * (x * x + y * y)]
+		]
+	}
+
+	fn dot(&self, rhs: &Quaternion) -> f64 {
+		self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+	}
+
+	fn negate(&self) -> Quaternion {
+		self.scale(-1.)
+	}
+
+	fn scale(&self, factor: f64) -> Quaternion {
+		Quaternion {
+			w: self.w * factor,
+			x: self.x * factor,
+			y: self.y * factor,
+			z: self.z * factor
+		}
+	}
+
+	fn add(&self, rhs: &Quaternion) -> Quaternion {
+		Quaternion {
+			w: self.w + rhs.w,
+			x: self.x + rhs.x,
+			y: self.y + rhs.y,
+			z: self.z + rhs.z
+		}
+	}
+
+	// Constant angular velocity between two unit quaternions, nearly equal ones are blended linearly
+	fn slerp(&self, rhs: &Quaternion, t: f64) -> Quaternion {
+		let cos_theta = f64::clamp(self.dot(rhs), -1., 1.);
+		let (a, b) = if cos_theta > 0.9995 {
+			(1. - t, t)
+		}
+		else {
+			let theta = f64::acos(cos_theta);
+			let sin_theta = f64::sin(theta);
+			(f64::sin((1. - t) * theta) / sin_theta, f64::sin(t * theta) / sin_theta)
+		};
+
+		let q = self.scale(a).add(&rhs.scale(b));
+		q.scale(1. / q.dot(&q).sqrt())
+	}
+}
+
+fn multiply(lhs: &Matrix3, rhs: &Matrix3) -> Matrix3 {
+	let mut result = [[0.; 3]; 3];
+	for (i, row) in result.iter_mut().enumerate() {
+		for (j, value) in row.iter_mut().enumerate() {
+			*value = (0..3).map(|k| lhs[i][k] * rhs[k][j]).sum();
+		}
+	}
+
+	result
+}
+
+fn transpose(m: &Matrix3) -> Matrix3 {
+	let mut result = [[0.; 3]; 3];
+	for (i, row) in result.iter_mut().enumerate() {
+		for (j, value) in row.iter_mut().enumerate() {
+			*value = m[j][i];
+		}
+	}
+
+	result
+}
+
+fn determinant(m: &Matrix3) -> f64 {
+	m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+		- m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+		+ m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::animated_transform::AnimatedTransform;
+	use crate::transform::Transform;
+	use crate::track::{Track, Interpolation};
+	use crate::vec3::{Vec3, Point3};
+	use crate::aabb::Aabb;
+
+	fn assert_near(a: Vec3, b: Vec3) {
+		assert!((a - b).length() < 1e-6, "{:?} != {:?}", a, b);
+	}
+
+	#[test]
+	fn ends() {
+		let start = Transform::translate(Vec3::new(1., 0., 0.)) * Transform::rotate(Vec3::new(0., 1., 0.), 30.);
+		let end = Transform::translate(Vec3::new(0., 2., 0.))
+			* Transform::rotate(Vec3::new(1., 0., 1.), 100.)
+			* Transform::scale(Vec3::new(2., 1., 0.5));
+		let animated = AnimatedTransform::new(start, end);
+		let p = Point3::new(0.3, -1., 2.);
+
+		assert_near(animated.at(0.).point(&p), start.point(&p));
+		assert_near(animated.at(1.).point(&p), end.point(&p));
+		assert_near(animated.at(1. - 1e-9).point(&p), end.point(&p));
+	}
+
+	#[test]
+	fn during() {
+		let start = Transform::translate(Vec3::new(0., 0., 0.));
+		let end = Transform::translate(Vec3::new(4., 0., 0.));
+		let animated = AnimatedTransform::new(start, end).during(2., 3.);
+		let p = Point3::new(0., 0., 0.);
+
+		assert_near(animated.at(1.).point(&p), p);
+		assert_near(animated.at(2.5).point(&p), Point3::new(2., 0., 0.));
+		assert_near(animated.at(10.).point(&p), Point3::new(4., 0., 0.));
+
+		let bbox = animated.bounding_box(&Aabb::from_points(p, p));
+		assert!(bbox.x.min <= 0. && bbox.x.max >= 4.);
+	}
+
+	#[test]
+	fn rotation_keeps_length() {
+		let animated = AnimatedTransform::new(Transform::identity(), Transform::rotate(Vec3::new(0., 0., 1.), 90.));
+
+		let p = animated.at(0.5).point(&Point3::new(1., 0., 0.));
+		let half = f64::sqrt(0.5);
+
+		assert_near(p, Point3::new(half, half, 0.));
+	}
+
+	#[test]
+	fn keyframed() {
+		let keys = [
+			Transform::identity(),
+			Transform::rotate(Vec3::new(0., 1., 0.), 120.),
+			Transform::translate(Vec3::new(0., 3., 0.)) * Transform::rotate(Vec3::new(0., 1., 0.), 240.)
+		];
+		let track = Track::new(Interpolation::CatmullRom).key(0., keys[0]).key(1., keys[1]).key(3., keys[2]);
+		let animated = AnimatedTransform::keyframed(&track);
+		let p = Point3::new(1., 0., 0.);
+
+		assert_near(animated.at(1.).point(&p), keys[1].point(&p));
+		assert_near(animated.at(5.).point(&p), keys[2].point(&p));
+		let middle = animated.at(2.);
+		let radius = (middle.point(&p) - middle.translation()).length();
+		assert!((radius - 1.).abs() < 1e-9);
+	}
+
+	#[test]
+	fn bounding_box_covers_motion() {
+		let animated = AnimatedTransform::new(
+			Transform::translate(Vec3::new(-1., 0., 0.)),
+			Transform::translate(Vec3::new(1., 0., 0.)) * Transform::rotate(Vec3::new(0., 0., 1.), 170.)
+		);
+		let object = Aabb::from_points(Point3::new(-1., -1., -1.), Point3::new(1., 1., 1.));
+		let bbox = animated.bounding_box(&object);
+
+		for step in 0..=1000 {
+			let swept = animated.at(f64::from(step) / 1000.).bounding_box(&object);
+			for corner in [swept.min(), swept.max()] {
+				assert!(bbox.x.contains(corner.x()) && bbox.y.contains(corner.y()) && bbox.z.contains(corner.z()));
+			}
+		}
+	}
+}
\ No newline at end of file
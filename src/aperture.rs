@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::texture::Texture;
+use crate::vec3::{Vec3, Point3};
+
+use rand::Rng;
+
+const MASK_TRIES: usize = 64;
+
+// Shape of the lens opening, which is also the shape of out of focus highlights
+#[derive(Clone)]
+pub enum Aperture {
+	Disk,
+	// Regular polygon inscribed in the disk, as made by the blades of a diaphragm, rotated in degrees
+	Polygon {
+		blades: u32,
+		rotation: f64
+	},
+	// Transmission read from the texture over the square around the disk, u going right and v up
+	Mask(Arc<dyn Texture>)
+}
+
+impl Aperture {
+	pub fn polygon(blades: u32, rotation: f64) -> Aperture {
+		Aperture::Polygon {
+			blades: u32::max(blades, 3),
+			rotation
+		}
+	}
+
+	pub fn mask(texture: Arc<dyn Texture>) -> Aperture {
+		Aperture::Mask(texture)
+	}
+
+	// Uniform point of the opening in [-1, 1] coordinates, a mask that rejects every try lets no light through
+	pub fn sample(&self) -> Option<(f64, f64)> {
+		let mut rng = rand::thread_rng();
+		match self {
+			Aperture::Disk => {
+				let p = Vec3::random_in_unit_disk();
+				Some((p.x(), p.y()))
+			},
+			Aperture::Polygon { blades, rotation } => {
+				// Blades split the polygon in equal triangles around the center
+				let step = 2. * PI / f64::from(*blades);
+				let start = rotation.to_radians() + f64::from(rng.gen_range(0..*blades)) * step;
+				let (a, b) = ((start.cos(), start.sin()), ((start + step).cos(), (start + step).sin()));
+
+				let (mut s, mut t) = (rng.gen::<f64>(), rng.gen::<f64>());
+				if s + t > 1. {
+					(s, t) = (1. - s, 1. - t);
+				}
+
+				Some((s * a.0 + t * b.0, s * a.1 + t * b.1))
+			},
+			Aperture::Mask(texture) => {
+				for _ in 0..MASK_TRIES {
+					let (u, v) = (rng.gen::<f64>(), rng.gen::<f64>());
+					let transmission = texture.value(u, v, &Point3::new(u, v, 0.));
+					let average = (transmission.x() + transmission.y() + transmission.z()) / 3.;
+
+					if rng.gen::<f64>() < average {
+						return Some((2. * u - 1., 2. * v - 1.));
+					}
+				}
+
+				None
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::aperture::Aperture;
+
+	#[test]
+	fn disk_covers_every_quadrant() {
+		let mut quadrants = [false; 4];
+		for _ in 0..1000 {
+			let (x, y) = Aperture::Disk.sample().unwrap();
+			assert!(x * x + y * y < 1.);
+			quadrants[usize::from(x > 0.) + 2 * usize::from(y > 0.)] = true;
+		}
+
+		assert!(quadrants.iter().all(|&quadrant| quadrant));
+	}
+
+	#[test]
+	fn polygon_stays_inside() {
+		// Inradius of a square inscribed in the unit circle
+		let aperture = Aperture::polygon(4, 45.);
+		let half = f64::sqrt(0.5);
+
+		for _ in 0..1000 {
+			let (x, y) = aperture.sample().unwrap();
+			assert!(x.abs() <= half + 1e-12 && y.abs() <= half + 1e-12);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use crate::cie;
+use crate::color::Color;
+use crate::vec3::Vec3;
+
+// Spectral radiance of a black body in W / (sr m² nm), `lambda` in nanometers
+pub fn planck(lambda: f64, temperature: f64) -> f64 {
+	if temperature <= 0. {
+		return 0.;
+	}
+
+	let c = 299792458.;
+	let h = 6.62606957e-34;
+	let kb = 1.3806488e-23;
+	let l = lambda * 1e-9;
+
+	let radiance = (2. * h * c * c) / (l.powi(5) * (f64::exp((h * c) / (l * kb * temperature)) - 1.));
+
+	radiance * 1e-9
+}
+
+pub fn xyz(temperature: f64) -> Vec3 {
+	let step = 5.;
+	let mut xyz = Vec3::new(0., 0., 0.);
+	let mut lambda = cie::LAMBDA_MIN;
+	while lambda <= cie::LAMBDA_MAX {
+		xyz += planck(lambda, temperature) * cie::color_matching(lambda) * step;
+		lambda += step;
+	}
+
+	xyz
+}
+
+// Colour of the black body with a luminance of one
+pub fn color(temperature: f64) -> Color {
+	let xyz = xyz(temperature);
+	if xyz.y() <= 0. {
+		return Color::new(0., 0., 0.);
+	}
+
+	positive(&cie::xyz_to_rgb(&(xyz / xyz.y())))
+}
+
+// Emitted radiance relative to a black body at 6500K, so hotter is brighter and cold emits nothing
+pub fn emission(temperature: f64) -> Color {
+	positive(&cie::xyz_to_rgb(&(xyz(temperature) / xyz(6500.).y())))
+}
+
+// Low temperatures fall outside of the sRGB gamut
+fn positive(color: &Color) -> Color {
+	Color::new(f64::max(color.x(), 0.), f64::max(color.y(), 0.), f64::max(color.z(), 0.))
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+
+// Step in uv used to differentiate the height texture
+const DELTA: f64 = 5e-4;
+
+// Grayscale bump map over any material, the first channel of `height` times `scale` displaces the
+// surface along its normal and the shading normal follows the displaced surface (Blinn 1978)
+pub struct BumpMap {
+	material: Arc<dyn Material>,
+	height: Arc<dyn Texture>,
+	scale: f64
+}
+
+impl Material for BumpMap {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let shading = self.shading(ray, rec);
+		let (scattered, attenuation) = self.material.scatter(ray, &shading)?;
+		if !shading.consistent(&scattered.direction()) {
+			return None;
+		}
+
+		Some((scattered, attenuation))
+	}
+
+	fn emitted(&self, rec: &HitRecord) -> Color {
+		self.material.emitted(rec)
+	}
+}
+
+impl BumpMap {
+	pub fn new(material: Arc<dyn Material>, height: Arc<dyn Texture>, scale: f64) -> BumpMap {
+		BumpMap {
+			material,
+			height,
+			scale
+		}
+	}
+
+	fn shading<'a>(&self, ray: &Ray, rec: &HitRecord<'a>) -> HitRecord<'a> {
+		// Shapes without a parameterisation are bumped along an arbitrary unit tangent frame
+		let frame = rec.tangent_frame();
+		let (dpdu, dpdv) = if rec.dpdu.near_zero() { (frame.u(), frame.v()) } else { (rec.dpdu, rec.dpdv) };
+		let normal = frame.w();
+
+		let height = |u: f64, v: f64, offset| self.scale * self.height.value(u, v, &(rec.point + offset)).x();
+		let displacement = height(rec.u, rec.v, Vec3::new(0., 0., 0.));
+		let u_displacement = height(rec.u + DELTA, rec.v, DELTA * dpdu);
+		let v_displacement = height(rec.u, rec.v + DELTA, DELTA * dpdv);
+
+		let bumped_dpdu = dpdu + (u_displacement - displacement) / DELTA * normal;
+		let bumped_dpdv = dpdv + (v_displacement - displacement) / DELTA * normal;
+		let mut bumped = bumped_dpdu.cross(bumped_dpdv);
+		if bumped.dot(normal) < 0. {
+			bumped = -bumped;
+		}
+		if bumped.near_zero() {
+			return *rec;
+		}
+
+		let mut shading = *rec;
+		shading.set_shading_normal(&bumped);
+		if ray.direction().dot(shading.normal) >= 0. {
+			return *rec;
+		}
+
+		shading
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::bump_map::BumpMap;
+	use crate::lambertian::Lambertian;
+	use crate::texture::Texture;
+	use crate::solid_color::SolidColor;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	// Height rising with u
+	struct Ramp;
+
+	impl Texture for Ramp {
+		fn value(&self, u: f64, _v: f64, _p: &Point3) -> Color {
+			Color::new(u, u, u)
+		}
+	}
+
+	fn shade(height: Arc<dyn Texture>) -> Vec3 {
+		let bump = BumpMap::new(Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))), height, 1.);
+		let ray = Ray::new(Point3::new(0., 0., 1.), Vec3::new(0., 0., -1.));
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., &bump);
+		record.set_face_normal(&ray, &Vec3::new(0., 0., 1.));
+		(record.u, record.v) = (0.5, 0.5);
+		record.dpdu = Vec3::new(0., 2., 0.);
+		record.dpdv = Vec3::new(-2., 0., 0.);
+
+		bump.shading(&ray, &record).normal
+	}
+
+	#[test]
+	fn constant_height() {
+		assert!((shade(Arc::new(SolidColor::new(Color::new(0.3, 0.3, 0.3)))) - Vec3::new(0., 0., 1.)).length() < 1e-12);
+	}
+
+	#[test]
+	fn slope_tilts_along_dpdu() {
+		// The displaced surface has tangents (0, 2, 1) and (-2, 0, 0), facing away from the rise
+		let tilted = shade(Arc::new(Ramp));
+		assert!((tilted - Vec3::new(0., -1., 2.).unit()).length() < 1e-6, "{:?}", tilted);
+	}
+}
\ No newline at end of file
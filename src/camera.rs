@@ -1,11 +1,28 @@
 use crate::ray::Ray;
 use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
 use crate::color::Color;
 use crate::interval::Interval;
 use crate::vec3::{Point3, Vec3};
+use crate::spectrum::{self, Wavelengths};
+use crate::henyey_greenstein::HenyeyGreenstein;
+use crate::camera_animation::CameraAnimation;
+use crate::image::Image;
+use crate::projection::Projection;
+use crate::stereo::{Stereo, StereoLayout};
+use crate::aperture::Aperture;
+use crate::lens::Lens;
+use crate::lens_system::LensSystem;
+use crate::exposure::Exposure;
 
 use rand::Rng;
 use std::f64;
+use std::io;
+use std::sync::Arc;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+const MAX_WALK_STEPS: usize = 4096;
 
 pub struct Camera {
 	pub samples_per_pixel: i16,
@@ -18,6 +35,27 @@ pub struct Camera {
 	pub up: Vec3,
 	pub defocus_angle: f64,
 	pub focus_dist: f64,
+	pub background: Option<Color>,
+	// Emitters with area sampling that bounces off diffuse materials aim for, e.g. a list of quads and disks
+	pub lights: Option<Arc<dyn Hittable>>,
+	pub spectral: bool,
+	pub projection: Projection,
+	pub stereo: Option<Stereo>,
+	pub aperture: Aperture,
+	// Shift of the lens barrel's shadow at the image corners in aperture radii, clipping out of focus
+	// highlights into cat's eyes towards the edges
+	pub cats_eye: f64,
+	// Overrides `fov`, `defocus_angle` and the exposure's f-number from a focal length and f-number
+	pub lens: Option<Lens>,
+	// Real lens traced in place of the thin lens, it sets `fov` and the exposure's f-number and ignores the
+	// aperture settings
+	pub lens_system: Option<LensSystem>,
+	// Film response in photographic units, radiance is written as is without it
+	pub exposure: Option<Exposure>,
+	// Pixel whose hit point sets `focus_dist` before rendering
+	pub autofocus: Option<(i32, i32)>,
+	pub shutter_open: f64,
+	pub shutter_close: f64,
 
 	image_height: i32,
 	center: Point3,
@@ -31,6 +69,12 @@ pub struct Camera {
 	defocus_disk_v: Vec3
 }
 
+impl Default for Camera {
+	fn default() -> Self {
+		Camera::new()
+	}
+}
+
 impl Camera {
 	pub fn new() -> Camera {
 		Camera {
@@ -44,6 +88,19 @@ impl Camera {
 			up: Vec3::new(0., 1., 0.),
 			defocus_angle: 0.,
 			focus_dist: 10.,
+			background: None,
+			lights: None,
+			spectral: false,
+			projection: Projection::Perspective,
+			stereo: None,
+			aperture: Aperture::Disk,
+			cats_eye: 0.,
+			lens: None,
+			lens_system: None,
+			exposure: None,
+			autofocus: None,
+			shutter_open: 0.,
+			shutter_close: 0.,
 
 			image_height: 100,
 			center: Vec3::new(0., 0., 0.),
@@ -58,32 +115,122 @@ impl Camera {
 		}
 	}
 
+	// Writes the image to the standard output as a PPM
 	pub fn render(&mut self, world: &dyn Hittable) {
+		let image = self.render_image(world);
+
+		image.write_ppm(&mut io::stdout().lock()).expect("Failed to write the image");
+	}
+
+	// Stereo rigs give both eyes at the configured resolution packed together
+	pub fn render_image(&mut self, world: &dyn Hittable) -> Image {
 		self.initialise();
+		if let Some((i, j)) = self.autofocus {
+			self.focus(world, i, j);
+			self.initialise();
+		}
+
+		let Some(stereo) = self.stereo else {
+			return self.render_eye(world, 0.);
+		};
 
-		println!("P3");
-    	println!("{} {}", self.image_width, self.image_height);
-    	println!("255");
+		let left = self.render_eye(world, -1.);
+		let right = self.render_eye(world, 1.);
+		let (width, height) = (left.width(), left.height());
 
-    	for j in 0..self.image_height {
-    	    eprint!("\rScanlines remaining: {} ", self.image_height - j);
-    	    for i in 0..self.image_width {
-    	        
-    	        let mut pixel_color = Color::new(0., 0., 0.);
+		let mut image = match stereo.layout {
+			StereoLayout::SideBySide => Image::new(2 * width, height),
+			StereoLayout::TopBottom => Image::new(width, 2 * height)
+		};
+		image.blit(&left, 0, 0);
+		match stereo.layout {
+			StereoLayout::SideBySide => image.blit(&right, width, 0),
+			StereoLayout::TopBottom => image.blit(&right, 0, height)
+		}
+
+		image
+	}
+
+	// `eye` is -1 for the left eye, 1 for the right one and 0 without stereo
+	fn render_eye(&self, world: &dyn Hittable, eye: f64) -> Image {
+		let mut image = Image::new(self.image_width as usize, self.image_height as usize);
+		for j in 0..self.image_height {
+			eprint!("\rScanlines remaining: {} ", self.image_height - j);
+			for i in 0..self.image_width {
+				let mut pixel_color = Color::new(0., 0., 0.);
 				for _ in 0..self.samples_per_pixel {
-					let ray = self.get_ray(i, j);
-					pixel_color += Camera::ray_color(&ray, self.max_depth, world);
+					let Some((ray, weight)) = self.get_ray(i, j, eye) else {
+						continue;
+					};
+					let radiance = self.ray_color(&ray, self.max_depth, world);
+					pixel_color += weight * match ray.wavelengths() {
+						Some(wavelengths) => spectrum::to_rgb(&radiance, &wavelengths),
+						None => radiance
+					};
 				}
 
-    	        pixel_color.write(self.samples_per_pixel);
-    	    }
-    	}
+				let mut pixel_color = pixel_color / f64::from(self.samples_per_pixel);
+				if let Some(exposure) = &self.exposure {
+					pixel_color = exposure.develop(&pixel_color, self.off_axis_cosine(i, j));
+				}
+
+				image.set(i as usize, j as usize, pixel_color);
+			}
+		}
 
 		eprintln!("\rDone                           ");
+
+		image
+	}
+
+	// Renders `frames` into `directory` as frame_0001.png, ... Frame n is shot at n / fps seconds, with the
+	// shutter interval taken relative to that time. Scene time is in seconds throughout: linear motions run
+	// over their first second unless given other times with `during`
+	pub fn render_frames(&mut self, world: &dyn Hittable, animation: &CameraAnimation, frames: RangeInclusive<u32>, fps: f64, directory: &Path) -> io::Result<()> {
+		let (shutter_open, shutter_close) = (self.shutter_open, self.shutter_close);
+
+		for frame in frames {
+			let time = f64::from(frame) / fps;
+			animation.apply(self, time);
+			self.shutter_open = time + shutter_open;
+			self.shutter_close = time + shutter_close;
+
+			eprintln!("Frame {}", frame);
+			let image = self.render_image(world);
+			image.save(&directory.join(format!("frame_{:04}.png", frame)))?;
+		}
+
+		self.shutter_open = shutter_open;
+		self.shutter_close = shutter_close;
+
+		Ok(())
 	}
 
 	fn initialise(&mut self) {
-		self.image_height = f64::max(f64::from(self.image_width) / self.aspect_ratio, 1.) as i32;
+		if let Some(stereo) = self.stereo {
+			assert!(stereo.convergence > 0., "stereo convergence distance must be positive");
+		}
+
+		if let Some(lens) = self.lens {
+			self.fov = lens.fov();
+			self.defocus_angle = lens.defocus_angle(self.focus_dist);
+		}
+		if let Some(system) = &mut self.lens_system {
+			system.focus(self.focus_dist);
+			self.fov = system.fov();
+		}
+		// The film is exposed through the lens that is actually there
+		if let Some(exposure) = &mut self.exposure {
+			if let Some(lens) = self.lens {
+				exposure.f_number = lens.f_number;
+			}
+			if let Some(system) = &self.lens_system {
+				exposure.f_number = system.f_number();
+			}
+		}
+
+		let aspect_ratio = self.projection.aspect_ratio().unwrap_or(self.aspect_ratio);
+		self.image_height = f64::max(f64::from(self.image_width) / aspect_ratio, 1.) as i32;
 
 		let theta = self.fov * f64::consts::PI / 180.;
 		let h = f64::tan(theta / 2.);
@@ -109,45 +256,380 @@ impl Camera {
 		self.defocus_disk_v = self.v * defocus_radius;
 	}
 
-	fn ray_color(ray: &Ray, depth: i8, world: &dyn Hittable) -> Color {
+	fn ray_color(&self, ray: &Ray, depth: i8, world: &dyn Hittable) -> Color {
 		if depth == 0 {
 			return Color::new(0., 0., 0.);
 		}
 
-		if let Some(record) = world.hit(ray, &Interval { min: 0.001, max: f64::MAX }) {
-			if let Some((scatter, attenuation)) = record.material.scatter(ray, &record) {
-				return attenuation * Camera::ray_color(&scatter, depth - 1, world);
+		// Scattering events inside a dielectric's medium form a random walk that does not use up the depth
+		let mut ray = *ray;
+		let mut throughput = Color::new(1., 1., 1.);
+		for _ in 0..MAX_WALK_STEPS {
+			let Some(record) = world.hit(&ray, &Interval { min: 0.001, max: f64::MAX }) else {
+				return throughput * self.background(&ray);
+			};
+
+			let (walk, transmittance) = Camera::free_flight(&ray, record.t, &throughput);
+			throughput = throughput * transmittance;
+			if let Some(walk) = walk {
+				ray = walk;
+				continue;
 			}
 
-			return Color::new(0., 0., 0.);
+			let emitted = Camera::spectrum(&record.material.emitted(&record), &ray);
+			if let Some((scatter, attenuation)) = record.material.scatter(&ray, &record) {
+				let (scatter, weight) = self.sample_lights(&ray, &record, scatter);
+				if weight <= 0. {
+					return throughput * emitted;
+				}
+
+				let attenuation = Camera::spectrum(&attenuation, &ray) * Camera::collapse_weight(&ray, &scatter) * weight;
+				return throughput * (emitted + attenuation * self.ray_color(&scatter, depth - 1, world));
+			}
+
+			return throughput * emitted;
+		}
+
+		Color::new(0., 0., 0.)
+	}
+
+	// Half of the bounces off materials with a scattering density head for the lights instead. Either way the
+	// weight divides by the mixture of both densities, so lights found by the material stay unbiased too
+	fn sample_lights(&self, ray: &Ray, record: &HitRecord, scatter: Ray) -> (Ray, f64) {
+		let Some(lights) = &self.lights else {
+			return (scatter, 1.);
+		};
+		if record.material.scattering_pdf(ray, record, &scatter.direction()) <= 0. {
+			return (scatter, 1.);
+		}
+
+		let scatter = if rand::thread_rng().gen::<f64>() < 0.5 { scatter.spawn(record.point, lights.random(&record.point)) } else { scatter };
+		let scattering_pdf = record.material.scattering_pdf(ray, record, &scatter.direction());
+		let mixture_pdf = 0.5 * scattering_pdf + 0.5 * lights.pdf_value(&record.point, &scatter.direction());
+		if mixture_pdf <= 0. {
+			return (scatter, 0.);
+		}
+
+		(scatter, scattering_pdf / mixture_pdf)
+	}
+
+	fn background(&self, ray: &Ray) -> Color {
+		// Without an explicit background the scene is lit by a sky gradient
+		if let Some(background) = self.background {
+			return Camera::spectrum(&background, ray);
 		}
 	
 		let unit_direction = ray.direction().unit();
 		let a = 0.5 * (unit_direction.y() + 1.);
 	
-		(1. - a) * Color::new(1., 1., 1.) + a * Color::new(0.5, 0.7, 1.)
+		Camera::spectrum(&((1. - a) * Color::new(1., 1., 1.) + a * Color::new(0.5, 0.7, 1.)), ray)
 	}
 
-	fn get_ray(&self, i: i32, j: i32) -> Ray {
-		let pixel_center = self.pixel00_loc + (self.pixel_delta_v * f64::from(j)) + (self.pixel_delta_u * f64::from(i));
-		let pixel_sample = pixel_center + self.pixel_sample_square();
+	// Materials and lights are described in RGB, uplifted to the path's wavelengths in spectral mode
+	fn spectrum(color: &Color, ray: &Ray) -> Color {
+		ray.wavelengths().map_or(*color, |wavelengths| spectrum::uplift(color, &wavelengths))
+	}
+
+	// When a scattering event keeps only the hero wavelength the companions are terminated,
+	// the hero carries their share to stay unbiased
+	fn collapse_weight(ray: &Ray, scatter: &Ray) -> Color {
+		match (ray.wavelengths(), scatter.wavelengths()) {
+			(Some(before), Some(after)) if !before.is_single() && after.is_single() => Color::new(3., 0., 0.),
+			_ => Color::new(1., 1., 1.)
+		}
+	}
+
+	// Travel through the dielectric medium the ray is in, up to the surface at `t`. A scattering medium may
+	// stop the ray earlier and send it in a new direction, otherwise this is Beer-Lambert attenuation
+	fn free_flight(ray: &Ray, t: f64, throughput: &Color) -> (Option<Ray>, Color) {
+		let Some(medium) = ray.media().current() else {
+			return (None, Color::new(1., 1., 1.));
+		};
+
+		let length = ray.direction().length();
+		let distance = t * length;
+		let scattering = Camera::spectrum(&medium.scattering, ray);
+		let extinction = Camera::spectrum(&medium.absorption, ray) + scattering;
+		let transmittance = |distance: f64| Color::new(
+			f64::exp(-extinction.x() * distance),
+			f64::exp(-extinction.y() * distance),
+			f64::exp(-extinction.z() * distance)
+		);
+
+		if scattering.x() <= 0. && scattering.y() <= 0. && scattering.z() <= 0. {
+			return (None, transmittance(distance));
+		}
+
+		// Distances are sampled with the extinction of one channel, picked proportionally to the path
+		// throughput, and the pdf is the mix over all channels so chromatic media stay unbiased
+		// (spectral MIS, Chiang et al. 2016)
+		let total = throughput.x() + throughput.y() + throughput.z();
+		if total <= 0. {
+			return (None, Color::new(0., 0., 0.));
+		}
+		let probabilities = *throughput / total;
+
+		let mut rng = rand::thread_rng();
+		let mut pick = rng.gen::<f64>();
+		let channel = (0..3).find(|&i| { pick -= probabilities.axis(i); pick < 0. }).unwrap_or(2);
+		let sigma = extinction.axis(channel);
+		let flight = if sigma > 0. { -f64::ln(1. - rng.gen::<f64>()) / sigma } else { f64::INFINITY };
+
+		if flight < distance {
+			let tr = transmittance(flight);
+			let pdf = probabilities.dot(extinction * tr);
+			let direction = HenyeyGreenstein::sample(&ray.direction(), medium.anisotropy);
+
+			return (Some(ray.spawn(ray.at(flight / length), direction)), scattering * tr / pdf);
+		}
 
-		let ray_origin = if self.defocus_angle <= 0. { self.center } else { self.defocus_disk_sample() };
-		let ray_direction = pixel_sample - ray_origin;
+		let tr = transmittance(distance);
+		(None, tr / probabilities.dot(tr))
+	}
+
+	// None where the projection covers no direction, e.g. outside of the fisheye circle
+	// The weight is the relative irradiance the ray carries to the film, 1 except through a lens system
+	fn get_ray(&self, i: i32, j: i32, eye: f64) -> Option<(Ray, f64)> {
+		let (px, py) = Camera::pixel_sample_square();
+		let pixel_sample = self.pixel00_loc + (self.pixel_delta_v * (f64::from(j) + py)) + (self.pixel_delta_u * (f64::from(i) + px));
+		let (half_ipd, convergence) = self.stereo.map_or((0., self.focus_dist), |stereo| (eye * stereo.ipd / 2., stereo.convergence));
+
+		let mut weight = 1.;
+		let (ray_origin, ray_direction) = match (self.projection, &self.lens_system) {
+			(Projection::Perspective, Some(system)) => {
+				let x = 2. * (f64::from(i) + 0.5 + px) / f64::from(self.image_width) - 1.;
+				let y = 1. - 2. * (f64::from(j) + 0.5 + py) / f64::from(self.image_height);
+				let (origin, direction, film_weight) = system.ray(x, y, f64::from(self.image_width) / f64::from(self.image_height))?;
+				weight = film_weight;
+
+				let to_world = |v: Vec3| v.x() * self.u + v.y() * self.v + v.z() * self.w;
+				(self.center + half_ipd * self.u + to_world(origin), to_world(direction))
+			},
+			(Projection::Perspective, None) => {
+				// Both eyes see the pixel at the same point of the zero parallax plane, then focus on the focal plane
+				let eye_center = self.center + half_ipd * self.u;
+				let target = self.center + (pixel_sample - self.center) * (convergence / self.focus_dist);
+				let focus = eye_center + (target - eye_center) * (self.focus_dist / convergence);
+
+				let x = 2. * (f64::from(i) + 0.5 + px) / f64::from(self.image_width) - 1.;
+				let y = 1. - 2. * (f64::from(j) + 0.5 + py) / f64::from(self.image_height);
+				let origin = if self.defocus_angle <= 0. { eye_center } else { self.defocus_disk_sample(eye_center, x, y)? };
+				(origin, focus - origin)
+			},
+			(Projection::Orthographic, _) => {
+				// Each eye looks along its own parallel direction, through the pixel on the zero parallax plane
+				let origin = pixel_sample + self.focus_dist * self.w + half_ipd * self.u;
+				(origin, -convergence * self.w - half_ipd * self.u)
+			},
+			_ => {
+				let x = (f64::from(i) + 0.5 + px) / f64::from(self.image_width);
+				let y = (f64::from(j) + 0.5 + py) / f64::from(self.image_height);
+				let aspect_ratio = f64::from(self.image_width) / f64::from(self.image_height);
+				let local = self.projection.direction(x, y, self.fov, aspect_ratio)?;
+				let direction = local.x() * self.u + local.y() * self.v + local.z() * self.w;
+
+				// The eye offset shrinks with the horizontal part of the direction, fading stereo out at the poles
+				let offset = half_ipd * direction.cross(self.v);
+				(self.center + offset, convergence * direction - offset)
+			}
+		};
+
+		// Every sample sees the scene at a random instant while the shutter is open
+		let mut rng = rand::thread_rng();
+		let time = self.shutter_open + rng.gen::<f64>() * (self.shutter_close - self.shutter_open);
 
-    	Ray::new(ray_origin, ray_direction)
+		let ray = Ray::new(ray_origin, ray_direction).with_time(time);
+		if self.spectral {
+			return Some((ray.with_wavelengths(Wavelengths::sample(rng.gen())), weight));
+		}
+
+		Some((ray, weight))
 	}
 
-	fn defocus_disk_sample(&self) -> Point3 {
-		let p = Vec3::random_in_unit_disk();
-		self.center + (p.x() * self.defocus_disk_u) + (p.y() * self.defocus_disk_v)
+	// Point on the aperture seen from the image point (x, y) in [-1, 1], None when the barrel blocks it
+	fn defocus_disk_sample(&self, center: Point3, x: f64, y: f64) -> Option<Point3> {
+		let (ax, ay) = self.aperture.sample()?;
+
+		if self.cats_eye > 0. {
+			// The barrel is an aperture sized disk sliding outwards with the image point, measured from the corners
+			let aspect_ratio = f64::from(self.image_width) / f64::from(self.image_height);
+			let scale = self.cats_eye / f64::sqrt(aspect_ratio * aspect_ratio + 1.);
+			let (bx, by) = (ax - scale * x * aspect_ratio, ay - scale * y);
+			if bx * bx + by * by > 1. {
+				return None;
+			}
+		}
+
+		Some(center + (ax * self.defocus_disk_u) + (ay * self.defocus_disk_v))
 	}
 
-	fn pixel_sample_square(&self) -> Vec3 {
+	// Sets the focus distance to the depth of what the center of pixel (i, j) sees, a miss keeps it
+	fn focus(&mut self, world: &dyn Hittable, i: i32, j: i32) {
+		let pixel_center = self.pixel00_loc + (self.pixel_delta_v * f64::from(j)) + (self.pixel_delta_u * f64::from(i));
+		let ray = Ray::new(self.center, pixel_center - self.center);
+
+		if let Some(record) = world.hit(&ray, &Interval { min: 0.001, max: f64::MAX }) {
+			let depth = (record.point - self.center).dot(-self.w);
+			if depth > 0. {
+				self.focus_dist = depth;
+			}
+		}
+	}
+
+	// Cosine of the angle between the optical axis and the line from the lens to the pixel center. Only a
+	// perspective camera has a flat film behind a single lens, a lens system weights its rays for the falloff
+	fn off_axis_cosine(&self, i: i32, j: i32) -> f64 {
+		if self.projection != Projection::Perspective || self.lens_system.is_some() {
+			return 1.;
+		}
+
+		let pixel_center = self.pixel00_loc + (self.pixel_delta_v * f64::from(j)) + (self.pixel_delta_u * f64::from(i));
+
+		(pixel_center - self.center).unit().dot(-self.w)
+	}
+
+	// Offset from the pixel center, in pixels
+	fn pixel_sample_square() -> (f64, f64) {
 		let mut rng = rand::thread_rng();
 		let px = -0.5 + rng.gen::<f64>();
 		let py = -0.5 + rng.gen::<f64>();
 
-		px * self.pixel_delta_u + py * self.pixel_delta_v
+		(px, py)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::camera::Camera;
+	use crate::color::Color;
+	use crate::diffuse_light::DiffuseLight;
+	use crate::exposure::Exposure;
+	use crate::hittable_list::HittableList;
+	use crate::lambertian::Lambertian;
+	use crate::lens::Lens;
+	use crate::projection::Projection;
+	use crate::quad::Quad;
+	use crate::stereo::Stereo;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn rig(projection: Projection) -> Camera {
+		let mut camera = Camera::new();
+		camera.image_width = 201;
+		camera.lookfrom = Point3::new(0., 0., 0.);
+		camera.lookat = Point3::new(0., 0., -1.);
+		camera.focus_dist = 10.;
+		camera.projection = projection;
+		camera.stereo = Some(Stereo::new(1., 5.));
+		camera.initialise();
+
+		camera
+	}
+
+	// Where the ray crosses the plane z = -depth
+	fn at_depth(ray: &Ray, depth: f64) -> Point3 {
+		ray.at((-depth - ray.origin().z()) / ray.direction().z())
+	}
+
+	// Rays of both eyes meet on the zero parallax plane, nearer or further they are apart by the disparity
+	fn assert_converges(projection: Projection, far_disparity: f64) {
+		let camera = rig(projection);
+		// Two independent pixel samples, each jittered by up to half a pixel on the focal plane
+		let pixel_size = camera.pixel_delta_u.length() + camera.pixel_delta_v.length();
+
+		for (i, j) in [(100, 100), (30, 170)] {
+			let (left, _) = camera.get_ray(i, j, -1.).unwrap();
+			let (right, _) = camera.get_ray(i, j, 1.).unwrap();
+
+			assert!((at_depth(&left, 5.) - at_depth(&right, 5.)).length() < 2. * pixel_size);
+			let disparity = at_depth(&right, 10.).x() - at_depth(&left, 10.).x();
+			assert!(f64::abs(disparity - far_disparity) < 2. * pixel_size, "{}", disparity);
+		}
+	}
+
+	#[test]
+	fn stereo_convergence() {
+		assert_converges(Projection::Perspective, -1.);
+		// Parallel rays still converge, with parallax growing linearly with depth
+		assert_converges(Projection::Orthographic, -1.);
+	}
+
+	#[test]
+	fn omni_directional_stereo() {
+		let camera = rig(Projection::Equirectangular);
+
+		for (i, j) in [(100, 50), (10, 20), (180, 70)] {
+			let (left, _) = camera.get_ray(i, j, -1.).unwrap();
+			let (right, _) = camera.get_ray(i, j, 1.).unwrap();
+
+			// Eyes sit on opposite sides of the viewing circle, up to the jitter of the pixel samples
+			assert!((left.origin() + right.origin()).length() < 0.05);
+			assert!(left.origin().length() <= 0.5 + 1e-9);
+
+			// and meet at the convergence distance, less than two pixels apart where the eyes are 1 apart
+			let (left_end, right_end) = (left.origin() + left.direction(), right.origin() + right.direction());
+			assert!((left_end - right_end).length() < 5. * 2. * std::f64::consts::PI / 201. * 2.);
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn zero_convergence() {
+		let mut camera = Camera::new();
+		camera.stereo = Some(Stereo { convergence: 0., ..Stereo::new(0.064, 1.) });
+		camera.initialise();
+	}
+
+	#[test]
+	fn exposure_through_the_lens() {
+		let mut camera = Camera::new();
+		camera.lens = Some(Lens::new(50., 2.8));
+		camera.exposure = Some(Exposure::new(100., 1. / 100., 16.).vignetting(1.));
+		camera.initialise();
+
+		assert_eq!(camera.exposure.unwrap().f_number, 2.8);
+		assert!(camera.off_axis_cosine(0, 0) < 1.);
+	}
+
+	#[test]
+	fn no_film_vignetting_off_perspective() {
+		for projection in [Projection::Orthographic, Projection::Equirectangular] {
+			let mut camera = Camera::new();
+			camera.projection = projection;
+			camera.initialise();
+
+			assert_eq!(camera.off_axis_cosine(0, 0), 1.);
+		}
+	}
+
+	// Mean direct light on a grey floor under a 0.2 wide square light one unit above
+	fn lit_floor(sample_lights: bool, samples: usize) -> f64 {
+		let light = || Quad::new(Point3::new(-0.1, 1., -0.1), Vec3::new(0.2, 0., 0.), Vec3::new(0., 0., 0.2), Arc::new(DiffuseLight::new(Color::new(10., 10., 10.))));
+		let mut world = HittableList::new();
+		world.push(Box::new(Quad::new(Point3::new(-5., 0., -5.), Vec3::new(10., 0., 0.), Vec3::new(0., 0., 10.), Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))))));
+		world.push(Box::new(light()));
+
+		let mut camera = Camera::new();
+		camera.background = Some(Color::new(0., 0., 0.));
+		if sample_lights {
+			camera.lights = Some(Arc::new(light()));
+		}
+
+		let ray = Ray::new(Point3::new(0.5, 0.5, 0.), Vec3::new(-0.5, -0.5, 0.));
+		(0..samples).map(|_| camera.ray_color(&ray, 2, &world).y()).sum::<f64>() / samples as f64
+	}
+
+	#[test]
+	fn light_sampling() {
+		// Albedo / pi times the radiance times the light's solid angle, less its cos^4 falloff
+		let expected = 0.5 / std::f64::consts::PI * 10. * 0.04 * (1. - 0.04 / 3.);
+
+		let sampled = lit_floor(true, 20000);
+		assert!(f64::abs(sampled - expected) < 0.03 * expected, "{} != {}", sampled, expected);
+		// Bounces that find the light on their own agree, only noisier
+		let found = lit_floor(false, 50000);
+		assert!(f64::abs(found - expected) < 0.15 * expected, "{} != {}", found, expected);
 	}
 }
\ No newline at end of file
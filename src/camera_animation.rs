@@ -0,0 +1,107 @@
+use crate::camera::Camera;
+use crate::track::{Track, Interpolation};
+use crate::transform::Transform;
+use crate::vec3::{Vec3, Point3};
+
+const TURNTABLE_KEYS: usize = 72;
+
+// Keyframe tracks for the camera, fields without a track keep the value set on the camera
+#[derive(Clone, Default)]
+pub struct CameraAnimation {
+	pub lookfrom: Option<Track<Point3>>,
+	pub lookat: Option<Track<Point3>>,
+	pub fov: Option<Track<f64>>,
+	pub focus_dist: Option<Track<f64>>,
+	pub defocus_angle: Option<Track<f64>>
+}
+
+impl CameraAnimation {
+	pub fn new() -> CameraAnimation {
+		CameraAnimation::default()
+	}
+
+	// Orbits `lookfrom` once around the `up` axis through `lookat` in `duration` seconds, looking at it all along
+	pub fn turntable(lookfrom: Point3, lookat: Point3, up: Vec3, duration: f64) -> CameraAnimation {
+		// One key past either end gives the spline its tangent at the start and the end of the turn
+		let mut track = Track::new(Interpolation::CatmullRom);
+		for i in -1..=TURNTABLE_KEYS as i32 + 1 {
+			let fraction = f64::from(i) / TURNTABLE_KEYS as f64;
+			let orbit = Transform::translate(lookat) * Transform::rotate(up, 360. * fraction) * Transform::translate(-lookat);
+
+			track = track.key(fraction * duration, orbit.point(&lookfrom));
+		}
+
+		CameraAnimation::new()
+			.lookfrom(track)
+			.lookat(Track::constant(lookat))
+	}
+
+	pub fn lookfrom(mut self, track: Track<Point3>) -> CameraAnimation {
+		self.lookfrom = Some(track);
+		self
+	}
+
+	pub fn lookat(mut self, track: Track<Point3>) -> CameraAnimation {
+		self.lookat = Some(track);
+		self
+	}
+
+	pub fn fov(mut self, track: Track<f64>) -> CameraAnimation {
+		self.fov = Some(track);
+		self
+	}
+
+	pub fn focus_dist(mut self, track: Track<f64>) -> CameraAnimation {
+		self.focus_dist = Some(track);
+		self
+	}
+
+	pub fn defocus_angle(mut self, track: Track<f64>) -> CameraAnimation {
+		self.defocus_angle = Some(track);
+		self
+	}
+
+	// Moves the camera to where its tracks are at `time`
+	pub fn apply(&self, camera: &mut Camera, time: f64) {
+		let at = |track: &Option<Track<f64>>, value: f64| track.as_ref().and_then(|track| track.at(time)).unwrap_or(value);
+		let at_point = |track: &Option<Track<Point3>>, value: Point3| track.as_ref().and_then(|track| track.at(time)).unwrap_or(value);
+
+		camera.lookfrom = at_point(&self.lookfrom, camera.lookfrom);
+		camera.lookat = at_point(&self.lookat, camera.lookat);
+		camera.fov = at(&self.fov, camera.fov);
+		camera.focus_dist = at(&self.focus_dist, camera.focus_dist);
+		camera.defocus_angle = at(&self.defocus_angle, camera.defocus_angle);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::camera::Camera;
+	use crate::camera_animation::CameraAnimation;
+	use crate::track::{Track, Interpolation};
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn turntable_stays_on_orbit() {
+		let lookat = Point3::new(1., 0., 0.);
+		let animation = CameraAnimation::turntable(Point3::new(1., 2., 5.), lookat, Vec3::new(0., 1., 0.), 4.);
+
+		let track = animation.lookfrom.unwrap();
+		for i in 0..1000 {
+			let distance = (track.at(4. * f64::from(i) / 1000.).unwrap() - Point3::new(1., 2., 0.)).length();
+			assert!((distance - 5.).abs() < 1e-5, "{}", distance);
+		}
+	}
+
+	#[test]
+	fn apply() {
+		let mut camera = Camera::new();
+		camera.fov = 50.;
+		let animation = CameraAnimation::new().focus_dist(Track::new(Interpolation::Linear).key(0., 2.).key(2., 4.));
+
+		animation.apply(&mut camera, 1.);
+
+		assert_eq!(camera.focus_dist, 3.);
+		assert_eq!(camera.fov, 50.);
+	}
+}
\ No newline at end of file
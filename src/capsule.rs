@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::polynomial::solve_quadratic;
+
+// Cylinder of `height` along the y axis closed by two half spheres, the bottom one centered on `center`.
+// It can be cut between `y_min` and `y_max`, from -radius to height + radius, and swept by `phi_max`
+pub struct Capsule {
+	center: Point3,
+	radius: f64,
+	height: f64,
+	y_min: f64,
+	y_max: f64,
+	phi_max: f64,
+	material: Arc<dyn Material>
+}
+
+impl Hittable for Capsule {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let o = ray.origin() - self.center;
+		let d = ray.direction();
+		let r2 = self.radius * self.radius;
+
+		let mut closest: Option<f64> = None;
+		let mut consider = |root: f64| {
+			let p = o + root * d;
+			let phi = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI);
+			if p.y() < self.y_min || p.y() > self.y_max || phi > self.phi_max {
+				return;
+			}
+
+			if t.surround(root) && closest.is_none_or(|best| root < best) {
+				closest = Some(root);
+			}
+		};
+
+		let a = d.x() * d.x() + d.z() * d.z();
+		let b = 2. * (o.x() * d.x() + o.z() * d.z());
+		let c = o.x() * o.x() + o.z() * o.z() - r2;
+		for root in solve_quadratic(a, b, c) {
+			let y = o.y() + root * d.y();
+			if (0. ..=self.height).contains(&y) {
+				consider(root);
+			}
+		}
+
+		for cap_y in [0., self.height] {
+			let oc = o - Vec3::new(0., cap_y, 0.);
+			for root in solve_quadratic(d.squared_length(), 2. * oc.dot(d), oc.squared_length() - r2) {
+				let y = o.y() + root * d.y();
+				if (cap_y == 0. && y <= 0.) || (cap_y > 0. && y >= self.height) {
+					consider(root);
+				}
+			}
+		}
+
+		let root = closest?;
+		let p = o + root * d;
+
+		let axis_point = Vec3::new(0., f64::clamp(p.y(), 0., self.height), 0.);
+		let outward_normal = (p - axis_point) / self.radius;
+
+		let mut record = HitRecord::new(ray.at(root), root, self.material.as_ref());
+		record.set_face_normal(ray, &outward_normal);
+		record.u = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI) / self.phi_max;
+		record.v = (p.y() - self.y_min) / (self.y_max - self.y_min);
+		record.dpdu = self.phi_max * Vec3::new(-p.z(), 0., p.x());
+
+		// v is linear in height, over the caps its derivative is approximated along the meridian
+		let up = Vec3::new(0., self.y_max - self.y_min, 0.);
+		record.dpdv = up - outward_normal.dot(up) * outward_normal;
+
+		Some(record)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		Aabb::from_points(
+			self.center + Vec3::new(-self.radius, self.y_min, -self.radius),
+			self.center + Vec3::new(self.radius, self.y_max, self.radius)
+		)
+	}
+}
+
+impl Capsule {
+	pub fn new(center: Point3, radius: f64, height: f64, material: Arc<dyn Material>) -> Capsule {
+		Capsule {
+			center,
+			radius,
+			height,
+			y_min: -radius,
+			y_max: height + radius,
+			phi_max: 2. * PI,
+			material
+		}
+	}
+
+	pub fn height_range(mut self, y_min: f64, y_max: f64) -> Capsule {
+		self.y_min = f64::clamp(f64::min(y_min, y_max), -self.radius, self.height + self.radius);
+		self.y_max = f64::clamp(f64::max(y_min, y_max), -self.radius, self.height + self.radius);
+		self
+	}
+
+	pub fn phi_max(mut self, degrees: f64) -> Capsule {
+		self.phi_max = f64::clamp(degrees, 0., 360.).to_radians();
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::capsule::Capsule;
+	use crate::hit_assertions::{material, hit, assert_hit};
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn side_and_poles() {
+		let capsule = Capsule::new(Point3::new(0., 0., 0.), 0.5, 1., material());
+
+		assert_hit(hit(&capsule, Point3::new(-5., 0.5, 0.), Vec3::new(1., 0., 0.)), 4.5, Vec3::new(-1., 0., 0.), true);
+		assert_hit(hit(&capsule, Point3::new(0., 5., 0.), Vec3::new(0., -1., 0.)), 3.5, Vec3::new(0., 1., 0.), true);
+		assert_hit(hit(&capsule, Point3::new(0., -5., 0.), Vec3::new(0., 1., 0.)), 4.5, Vec3::new(0., -1., 0.), true);
+	}
+
+	#[test]
+	fn height_range_and_sweep() {
+		let cut = Capsule::new(Point3::new(0., 0., 0.), 0.5, 1., material()).height_range(-0.5, 1.);
+
+		// The top half sphere is cut away, the ray falls through to the inside of the bottom one
+		assert_hit(hit(&cut, Point3::new(0., 5., 0.), Vec3::new(0., -1., 0.)), 5.5, Vec3::new(0., 1., 0.), false);
+		let record = cut.hit(&Ray::new(Point3::new(-5., 1., 0.), Vec3::new(1., 0., 0.)), &Interval::new(0.001, f64::MAX)).unwrap();
+		assert!(f64::abs(record.v - 1.) < 1e-9);
+
+		let half = Capsule::new(Point3::new(0., 0., 0.), 0.5, 1., material()).phi_max(180.);
+		assert_hit(hit(&half, Point3::new(0., 0.5, -5.), Vec3::new(0., 0., 1.)), 5.5, Vec3::new(0., 0., -1.), false);
+	}
+}
\ No newline at end of file
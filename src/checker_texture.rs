@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+use crate::color::Color;
+use crate::vec3::Point3;
+
+// 3D checker pattern, independent of the object UVs
+pub struct CheckerTexture {
+	inv_scale: f64,
+	even: Arc<dyn Texture>,
+	odd: Arc<dyn Texture>
+}
+
+impl Texture for CheckerTexture {
+	fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+		let x = f64::floor(self.inv_scale * p.x()) as i64;
+		let y = f64::floor(self.inv_scale * p.y()) as i64;
+		let z = f64::floor(self.inv_scale * p.z()) as i64;
+
+		if (x + y + z) % 2 == 0 {
+			self.even.value(u, v, p)
+		}
+		else {
+			self.odd.value(u, v, p)
+		}
+	}
+}
+
+impl CheckerTexture {
+	pub fn new(scale: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> CheckerTexture {
+		CheckerTexture {
+			inv_scale: 1. / scale,
+			even,
+			odd
+		}
+	}
+
+	pub fn from_colors(scale: f64, even: Color, odd: Color) -> CheckerTexture {
+		CheckerTexture::new(scale, Arc::new(SolidColor::new(even)), Arc::new(SolidColor::new(odd)))
+	}
+}
\ No newline at end of file
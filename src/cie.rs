@@ -0,0 +1,62 @@
+use crate::color::Color;
+use crate::vec3::Vec3;
+
+pub const LAMBDA_MIN: f64 = 380.;
+pub const LAMBDA_MAX: f64 = 780.;
+
+// White point of sRGB, with a luminance of one
+pub const D65: Vec3 = Vec3::new(0.95047, 1., 1.08883);
+
+// Multi-lobe Gaussian fit of the CIE 1931 colour matching functions (Wyman, Sloan and Shirley 2013)
+pub fn color_matching(lambda: f64) -> Vec3 {
+	let x = 1.056 * lobe(lambda, 599.8, 37.9, 31.0) + 0.362 * lobe(lambda, 442.0, 16.0, 26.7) - 0.065 * lobe(lambda, 501.1, 20.4, 26.2);
+	let y = 0.821 * lobe(lambda, 568.8, 46.9, 40.5) + 0.286 * lobe(lambda, 530.9, 16.3, 31.1);
+	let z = 1.217 * lobe(lambda, 437.0, 11.8, 36.0) + 0.681 * lobe(lambda, 459.0, 26.0, 13.8);
+
+	Vec3::new(x, y, z)
+}
+
+// Linear sRGB with a D65 white point
+pub fn xyz_to_rgb(xyz: &Vec3) -> Color {
+	Color::new(
+		3.2404542 * xyz.x() - 1.5371385 * xyz.y() - 0.4985314 * xyz.z(),
+		-0.9692660 * xyz.x() + 1.8760108 * xyz.y() + 0.0415560 * xyz.z(),
+		0.0556434 * xyz.x() - 0.2040259 * xyz.y() + 1.0572252 * xyz.z()
+	)
+}
+
+pub fn rgb_to_xyz(rgb: &Color) -> Vec3 {
+	Vec3::new(
+		0.4124564 * rgb.x() + 0.3575761 * rgb.y() + 0.1804375 * rgb.z(),
+		0.2126729 * rgb.x() + 0.7151522 * rgb.y() + 0.0721750 * rgb.z(),
+		0.0193339 * rgb.x() + 0.1191920 * rgb.y() + 0.9503041 * rgb.z()
+	)
+}
+
+// Bradford chromatic adaptation of a colour seen under the `source` white to how it looks under `target`
+pub fn adapt(xyz: &Vec3, source: &Vec3, target: &Vec3) -> Vec3 {
+	let (lms, source, target) = (bradford(xyz), bradford(source), bradford(target));
+	let adapted = Vec3::new(lms.x() * target.x() / source.x(), lms.y() * target.y() / source.y(), lms.z() * target.z() / source.z());
+
+	Vec3::new(
+		0.9869929 * adapted.x() - 0.1470543 * adapted.y() + 0.1599627 * adapted.z(),
+		0.4323053 * adapted.x() + 0.5183603 * adapted.y() + 0.0492912 * adapted.z(),
+		-0.0085287 * adapted.x() + 0.0400428 * adapted.y() + 0.9684867 * adapted.z()
+	)
+}
+
+// Sharpened cone responses
+fn bradford(xyz: &Vec3) -> Vec3 {
+	Vec3::new(
+		0.8951 * xyz.x() + 0.2664 * xyz.y() - 0.1614 * xyz.z(),
+		-0.7502 * xyz.x() + 1.7135 * xyz.y() + 0.0367 * xyz.z(),
+		0.0389 * xyz.x() - 0.0685 * xyz.y() + 1.0296 * xyz.z()
+	)
+}
+
+fn lobe(lambda: f64, mu: f64, sigma_low: f64, sigma_high: f64) -> f64 {
+	let sigma = if lambda < mu { sigma_low } else { sigma_high };
+	let t = (lambda - mu) / sigma;
+
+	f64::exp(-0.5 * t * t)
+}
\ No newline at end of file
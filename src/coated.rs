@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::fresnel;
+
+use rand::Rng;
+
+const MAX_BOUNCES: usize = 16;
+
+// Smooth dielectric layer over any base material, light is followed as a random walk between
+// the coat interface and the base until it leaves or is absorbed
+pub struct Coated {
+	base: Arc<dyn Material>,
+	ior: f64,
+	thickness: f64,
+	absorption: Color
+}
+
+impl Material for Coated {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let mut rng = rand::thread_rng();
+		let normal = rec.normal;
+		let unit_direction = ray.direction().unit();
+
+		let cos_theta = f64::min((-unit_direction).dot(normal), 1.);
+		if rng.gen::<f64>() < fresnel::dielectric(cos_theta, self.ior) {
+			return Some((ray.spawn(rec.point, unit_direction.reflect(&normal)), Color::new(1., 1., 1.)));
+		}
+
+		let mut direction = unit_direction.refract(&normal, 1. / self.ior);
+		let mut attenuation = self.transmittance(&direction, &normal);
+		let mut inner = *ray;
+		for _ in 0..MAX_BOUNCES {
+			let (scattered, base_attenuation) = self.base.scatter(&inner.spawn(rec.point, direction), rec)?;
+			attenuation = attenuation * base_attenuation;
+
+			// Light going into the base, e.g. through a dielectric, leaves the layer there
+			let up = scattered.direction().unit();
+			if up.dot(normal) <= 0. {
+				return Some((scattered, attenuation));
+			}
+
+			attenuation = attenuation * self.transmittance(&up, &normal);
+			let cos_inside = up.dot(normal);
+			if rng.gen::<f64>() >= fresnel::dielectric(cos_inside, 1. / self.ior) {
+				return Some((scattered.spawn(rec.point, up.refract(&-normal, self.ior)), attenuation));
+			}
+
+			// Reflected back down by the inside of the coat
+			direction = up.reflect(&-normal);
+			attenuation = attenuation * self.transmittance(&direction, &normal);
+			inner = scattered;
+		}
+
+		None
+	}
+
+	fn emitted(&self, rec: &HitRecord) -> Color {
+		self.base.emitted(rec)
+	}
+}
+
+impl Coated {
+	pub fn new(base: Arc<dyn Material>, ior: f64) -> Coated {
+		Coated {
+			base,
+			ior,
+			thickness: 0.,
+			absorption: Color::new(0., 0., 0.)
+		}
+	}
+
+	pub fn thickness(mut self, thickness: f64) -> Coated {
+		self.thickness = thickness;
+		self
+	}
+
+	// Beer-Lambert absorption coefficient per unit length travelled inside the coat
+	pub fn absorption(mut self, absorption: Color) -> Coated {
+		self.absorption = absorption;
+		self
+	}
+
+	// Attenuation over one crossing of the coat, the path length grows at grazing angles
+	fn transmittance(&self, direction: &Vec3, normal: &Vec3) -> Color {
+		let cosine = f64::max(direction.dot(*normal).abs(), 1e-4);
+		let distance = self.thickness / cosine;
+
+		Color::new(
+			f64::exp(-self.absorption.x() * distance),
+			f64::exp(-self.absorption.y() * distance),
+			f64::exp(-self.absorption.z() * distance)
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::coated::Coated;
+	use crate::material::Material;
+	use crate::metal::Metal;
+	use crate::lambertian::Lambertian;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn albedos(coated: &Coated, samples: usize) -> Vec<f64> {
+		let ray = Ray::new(Point3::new(0., 0., 1.), Vec3::new(0., 0., -1.));
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., coated);
+		record.normal = Vec3::new(0., 0., 1.);
+		record.front_face = true;
+
+		(0..samples).map(|_| coated.scatter(&ray, &record).map_or(0., |(_, attenuation)| attenuation.y())).collect()
+	}
+
+	#[test]
+	fn absorption_both_ways() {
+		// A mirror base sends light straight back, every trip through the coat is a crossing down and one up
+		let mirror = Coated::new(Arc::new(Metal::new(Color::new(1., 1., 1.), 0.)), 1.5).thickness(1.).absorption(Color::new(0.5, 0.5, 0.5));
+		let round_trip = f64::exp(-1.);
+		let r = 0.04;
+
+		let samples = albedos(&mirror, 20000);
+		for &albedo in &samples {
+			let trips = f64::ln(albedo) / f64::ln(round_trip);
+			assert!(albedo == 1. || (trips >= 1. - 1e-9 && f64::abs(trips - f64::round(trips)) < 1e-9), "{}", albedo);
+		}
+
+		// Reflected by the coat, or through it with any number of inner reflections
+		let expected = r + (1. - r) * (1. - r) * round_trip / (1. - r * round_trip);
+		let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+		assert!(f64::abs(mean - expected) < 0.01, "{} != {}", mean, expected);
+	}
+
+	#[test]
+	fn conserves_energy() {
+		let white = Coated::new(Arc::new(Lambertian::new(Color::new(1., 1., 1.))), 1.5);
+		let samples = albedos(&white, 20000);
+
+		assert!(samples.iter().all(|&albedo| albedo <= 1.));
+		let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+		assert!(mean > 0.9 && mean <= 1., "{}", mean);
+	}
+}
\ No newline at end of file
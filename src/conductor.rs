@@ -0,0 +1,165 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::microfacet::Ggx;
+use crate::onb::Onb;
+use crate::fresnel;
+use crate::thin_film::ThinFilm;
+
+use rand::Rng;
+
+// Rough metal with a GGX microfacet distribution and the Fresnel reflectance of a complex IOR
+pub struct Conductor {
+	eta: Color,
+	k: Color,
+	distribution: Ggx,
+	thin_film: Option<ThinFilm>
+}
+
+impl Material for Conductor {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let onb = Onb::with_tangent(&rec.normal, &rec.dpdu);
+		let wo = onb.to_local(&-ray.direction().unit());
+		if wo.z() <= 0. {
+			return None;
+		}
+
+		let mut rng = rand::thread_rng();
+		let m = if self.distribution.is_smooth() {
+			Vec3::new(0., 0., 1.)
+		}
+		else {
+			self.distribution.sample_visible_normal(&wo, rng.gen(), rng.gen())
+		};
+
+		let wi = 2. * wo.dot(m) * m - wo;
+		if wi.z() <= 0. {
+			return None;
+		}
+
+		// Sampling the visible normals leaves F * G2 / G1 as the weight
+		let fresnel = match &self.thin_film {
+			Some(film) => film.reflectance(ray, rec, wo.dot(m), 1., &self.eta, &self.k),
+			None => fresnel::conductor(wo.dot(m), &self.eta, &self.k)
+		};
+		let weight = if self.distribution.is_smooth() { 1. } else { self.distribution.g2(&wo, &wi) / self.distribution.g1(&wo) };
+
+		// Film reflectance of a spectral path is only known at the hero wavelength
+		let mut scattered = ray.spawn(rec.point, onb.local(&wi));
+		if let Some(wavelengths) = ray.wavelengths().filter(|_| self.thin_film.is_some()) {
+			scattered = scattered.with_wavelengths(wavelengths.collapse());
+		}
+
+		Some((scattered, fresnel * weight))
+	}
+}
+
+impl Conductor {
+	pub fn new(eta: Color, k: Color, roughness: f64) -> Conductor {
+		Conductor::anisotropic(eta, k, roughness, roughness)
+	}
+
+	// Roughness along the two tangent directions of the surface
+	pub fn anisotropic(eta: Color, k: Color, roughness_u: f64, roughness_v: f64) -> Conductor {
+		Conductor {
+			eta,
+			k,
+			distribution: Ggx::from_roughness(roughness_u, roughness_v),
+			thin_film: None
+		}
+	}
+
+	// Interference coating such as an oxide layer
+	pub fn thin_film(mut self, thin_film: ThinFilm) -> Conductor {
+		self.thin_film = Some(thin_film);
+		self
+	}
+
+	// Complex IORs sampled at 650nm, 550nm and 450nm
+	pub fn gold(roughness: f64) -> Conductor {
+		Conductor::new(Color::new(0.143119, 0.374957, 1.44248), Color::new(3.98316, 2.38572, 1.60322), roughness)
+	}
+
+	pub fn copper(roughness: f64) -> Conductor {
+		Conductor::new(Color::new(0.200438, 0.924033, 1.10221), Color::new(3.91295, 2.45285, 2.14219), roughness)
+	}
+
+	pub fn aluminum(roughness: f64) -> Conductor {
+		Conductor::new(Color::new(1.65746, 0.880369, 0.521229), Color::new(9.22387, 6.26952, 4.837), roughness)
+	}
+
+	pub fn silver(roughness: f64) -> Conductor {
+		Conductor::new(Color::new(0.155265, 0.116723, 0.138342), Color::new(4.82835, 3.12225, 2.14696), roughness)
+	}
+
+	pub fn chromium(roughness: f64) -> Conductor {
+		Conductor::new(Color::new(4.36968, 2.9167, 1.6547), Color::new(5.20643, 4.23136, 3.75495), roughness)
+	}
+
+	pub fn iron(roughness: f64) -> Conductor {
+		Conductor::new(Color::new(2.9114, 2.9497, 2.5845), Color::new(3.0893, 2.9318, 2.7670), roughness)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::conductor::Conductor;
+	use crate::material::Material;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	// Mirror Fresnel, a huge extinction reflects everything at every angle
+	fn perfect(roughness_u: f64, roughness_v: f64) -> Conductor {
+		Conductor::anisotropic(Color::new(1., 1., 1.), Color::new(1e6, 1e6, 1e6), roughness_u, roughness_v)
+	}
+
+	fn scatter(conductor: &Conductor, direction: Vec3, tangent: Vec3, samples: usize) -> Vec<(Vec3, f64)> {
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., conductor);
+		record.normal = Vec3::new(0., 0., 1.);
+		record.dpdu = tangent;
+		let ray = Ray::new(Point3::new(0., 0., 0.) - direction, direction);
+
+		(0..samples)
+			.filter_map(|_| conductor.scatter(&ray, &record))
+			.map(|(scattered, weight)| (scattered.direction(), weight.y()))
+			.collect()
+	}
+
+	#[test]
+	fn white_furnace() {
+		let direction = Vec3::new(1., 0., -1.).unit();
+		let albedo = |roughness: f64| scatter(&perfect(roughness, roughness), direction, Vec3::new(1., 0., 0.), 20000)
+			.iter()
+			.map(|(_, weight)| weight)
+			.sum::<f64>() / 20000.;
+
+		// Smooth surfaces keep all the energy, rough ones lose what a single scattering model leaves out:
+		// integrating D G2 / (4 cos) at alpha 0.64 and 45 degrees gives 0.581
+		let smooth = albedo(0.05);
+		assert!(smooth > 0.99 && smooth <= 1. + 1e-9, "{}", smooth);
+		let rough = albedo(0.8);
+		assert!(f64::abs(rough - 0.581) < 0.02, "{}", rough);
+	}
+
+	#[test]
+	fn anisotropy_follows_the_tangent() {
+		let spread = |tangent: Vec3| {
+			let directions = scatter(&perfect(0.8, 0.1), Vec3::new(0., 0., -1.), tangent, 5000);
+			let n = directions.len() as f64;
+			(
+				directions.iter().map(|(d, _)| d.unit().x() * d.unit().x()).sum::<f64>() / n,
+				directions.iter().map(|(d, _)| d.unit().y() * d.unit().y()).sum::<f64>() / n
+			)
+		};
+
+		let (along_x, along_y) = spread(Vec3::new(1., 0., 0.));
+		assert!(along_x > 5. * along_y, "{} {}", along_x, along_y);
+		// Turning the tangent swaps the axes of the lobe
+		let (along_x, along_y) = spread(Vec3::new(0., 1., 0.));
+		assert!(along_y > 5. * along_x, "{} {}", along_x, along_y);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::polynomial::solve_quadratic;
+
+// Cone around the y axis with its base on `center` and its apex at `center + height`,
+// optionally cut between `y_min` and `y_max` to make a frustum
+pub struct Cone {
+	center: Point3,
+	radius: f64,
+	height: f64,
+	y_min: f64,
+	y_max: f64,
+	phi_max: f64,
+	capped: bool,
+	material: Arc<dyn Material>
+}
+
+impl Hittable for Cone {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let o = ray.origin() - self.center;
+		let d = ray.direction();
+
+		// (t, outward normal, u, v, dp/dv) of the closest valid hit
+		let mut closest: Option<(f64, Vec3, f64, f64, Vec3)> = None;
+		let mut consider = |root: f64, normal: Vec3, u: f64, v: f64, dpdv: Vec3| {
+			if t.surround(root) && closest.is_none_or(|(best, ..)| root < best) {
+				closest = Some((root, normal, u, v, dpdv));
+			}
+		};
+
+		let k = (self.radius / self.height) * (self.radius / self.height);
+		let h = self.height - o.y();
+		let a = d.x() * d.x() + d.z() * d.z() - k * d.y() * d.y();
+		let b = 2. * (o.x() * d.x() + o.z() * d.z() + k * d.y() * h);
+		let c = o.x() * o.x() + o.z() * o.z() - k * h * h;
+		for root in solve_quadratic(a, b, c) {
+			let p = o + root * d;
+			let phi = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI);
+			if p.y() < self.y_min || p.y() > self.y_max || phi > self.phi_max {
+				continue;
+			}
+
+			let normal = Vec3::new(p.x(), k * (self.height - p.y()), p.z()).unit();
+			let v = (p.y() - self.y_min) / (self.y_max - self.y_min);
+			let slope = self.radius / self.height;
+			let dpdv = (self.y_max - self.y_min) * Vec3::new(-slope * f64::cos(phi), 1., -slope * f64::sin(phi));
+			consider(root, normal, phi / self.phi_max, v, dpdv);
+		}
+
+		if self.capped && f64::abs(d.y()) > 1e-8 {
+			for (y, normal) in [(self.y_min, Vec3::new(0., -1., 0.)), (self.y_max, Vec3::new(0., 1., 0.))] {
+				let cap_radius = self.radius * (self.height - y) / self.height;
+				if cap_radius <= 0. {
+					continue;
+				}
+
+				let root = (y - o.y()) / d.y();
+				let p = o + root * d;
+				let r = f64::sqrt(p.x() * p.x() + p.z() * p.z());
+				let phi = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI);
+				if r > cap_radius || phi > self.phi_max {
+					continue;
+				}
+
+				let radial = Vec3::new(f64::cos(phi), 0., f64::sin(phi));
+				consider(root, normal, phi / self.phi_max, r / cap_radius, cap_radius * radial);
+			}
+		}
+
+		let (root, outward_normal, u, v, dpdv) = closest?;
+		let p = o + root * d;
+
+		let mut record = HitRecord::new(ray.at(root), root, self.material.as_ref());
+		record.set_face_normal(ray, &outward_normal);
+		record.u = u;
+		record.v = v;
+		record.dpdu = self.phi_max * Vec3::new(-p.z(), 0., p.x());
+		record.dpdv = dpdv;
+
+		Some(record)
+	}
+
+	// Bounds the full sweep whatever `phi_max` is, which is conservative for partial ones
+	fn bounding_box(&self) -> Aabb {
+		let extent = self.radius * (self.height - self.y_min) / self.height;
+
+		Aabb::from_points(
+			self.center + Vec3::new(-extent, self.y_min, -extent),
+			self.center + Vec3::new(extent, self.y_max, extent)
+		)
+	}
+}
+
+impl Cone {
+	pub fn new(center: Point3, radius: f64, height: f64, material: Arc<dyn Material>) -> Cone {
+		Cone {
+			center,
+			radius,
+			height,
+			y_min: 0.,
+			y_max: height,
+			phi_max: 2. * PI,
+			capped: true,
+			material
+		}
+	}
+
+	pub fn height_range(mut self, y_min: f64, y_max: f64) -> Cone {
+		self.y_min = f64::clamp(f64::min(y_min, y_max), 0., self.height);
+		self.y_max = f64::clamp(f64::max(y_min, y_max), 0., self.height);
+		self
+	}
+
+	pub fn phi_max(mut self, degrees: f64) -> Cone {
+		self.phi_max = f64::clamp(degrees, 0., 360.).to_radians();
+		self
+	}
+
+	pub fn capped(mut self, capped: bool) -> Cone {
+		self.capped = capped;
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::cone::Cone;
+	use crate::hit_assertions::{material, hit, assert_hit};
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn side_and_base() {
+		let cone = Cone::new(Point3::new(0., 0., 0.), 1., 2., material());
+
+		// Halfway up the radius is 0.5, the normal leans up by the slope of the side
+		assert_hit(hit(&cone, Point3::new(-5., 1., 0.), Vec3::new(1., 0., 0.)), 4.5, Vec3::new(-1., 0.5, 0.), true);
+		assert_hit(hit(&cone, Point3::new(0.2, -5., 0.), Vec3::new(0., 1., 0.)), 5., Vec3::new(0., -1., 0.), true);
+		assert!(hit(&cone, Point3::new(-5., 2.5, 0.), Vec3::new(1., 0., 0.)).is_none());
+	}
+
+	#[test]
+	fn frustum() {
+		let frustum = Cone::new(Point3::new(0., 0., 0.), 1., 2., material()).height_range(0.5, 1.5).capped(false);
+
+		assert!(hit(&frustum, Point3::new(-5., 0.25, 0.), Vec3::new(1., 0., 0.)).is_none());
+		assert!(hit(&frustum, Point3::new(-5., 1.75, 0.), Vec3::new(1., 0., 0.)).is_none());
+		assert_hit(hit(&frustum, Point3::new(-5., 1., 0.), Vec3::new(1., 0., 0.)), 4.5, Vec3::new(-1., 0.5, 0.), true);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::vec3::Vec3;
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::color::Color;
+use crate::isotropic::Isotropic;
+
+use rand::Rng;
+
+// Homogeneous volume filling a closed boundary, rays scatter after an exponentially distributed distance
+pub struct ConstantMedium {
+	boundary: Arc<dyn Hittable>,
+	neg_inv_density: f64,
+	phase_function: Arc<dyn Material>
+}
+
+impl Hittable for ConstantMedium {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let length = ray.direction().length();
+		let mut hit_distance = self.neg_inv_density * f64::ln(rand::thread_rng().gen::<f64>());
+
+		// Non convex boundaries give several spans, the free flight distance carries over between them
+		for (enter, exit) in self.boundary.spans(ray) {
+			let t_enter = f64::max(enter.t, t.min);
+			let t_exit = f64::min(exit.t, t.max);
+			if t_enter >= t_exit {
+				continue;
+			}
+
+			let distance_inside = (t_exit - t_enter) * length;
+			if hit_distance > distance_inside {
+				hit_distance -= distance_inside;
+				continue;
+			}
+
+			let root = t_enter + hit_distance / length;
+
+			// Normal and face are arbitrary inside a volume
+			let mut record = HitRecord::new(ray.at(root), root, self.phase_function.as_ref());
+			record.normal = Vec3::new(1., 0., 0.);
+			record.front_face = true;
+
+			return Some(record);
+		}
+
+		None
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.boundary.bounding_box()
+	}
+}
+
+impl ConstantMedium {
+	pub fn new(boundary: Arc<dyn Hittable>, density: f64, albedo: Color) -> ConstantMedium {
+		ConstantMedium::with_phase(boundary, density, Arc::new(Isotropic::new(albedo)))
+	}
+
+	pub fn with_phase(boundary: Arc<dyn Hittable>, density: f64, phase_function: Arc<dyn Material>) -> ConstantMedium {
+		ConstantMedium {
+			boundary,
+			neg_inv_density: -1. / density,
+			phase_function
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::constant_medium::ConstantMedium;
+	use crate::cuboid::Cuboid;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn free_path() {
+		let boundary = Arc::new(Cuboid::new(Point3::new(0., 0., 0.), Point3::new(1., 1., 1.), Arc::new(Lambertian::new(Color::new(1., 1., 1.)))));
+		let medium = ConstantMedium::new(boundary, 2., Color::new(1., 1., 1.));
+		// Not normalised, distances are still measured along the ray
+		let ray = Ray::new(Point3::new(-1., 0.5, 0.5), Vec3::new(2., 0., 0.));
+
+		let samples = 20000;
+		let (mut through, mut near) = (0, 0);
+		for _ in 0..samples {
+			match medium.hit(&ray, &Interval::new(0.001, f64::MAX)) {
+				None => through += 1,
+				Some(record) if record.point.x() < 0.5 => near += 1,
+				Some(_) => ()
+			}
+		}
+
+		// Transmittance is exp(-density t), over the whole unit and over its first half
+		assert!(f64::abs(through as f64 / samples as f64 - f64::exp(-2.)) < 0.015);
+		assert!(f64::abs(near as f64 / samples as f64 - (1. - f64::exp(-1.))) < 0.015);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::aabb::Aabb;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CsgOperation {
+	Union,
+	Intersection,
+	Difference
+}
+
+impl CsgOperation {
+	fn inside(&self, in_left: bool, in_right: bool) -> bool {
+		match self {
+			CsgOperation::Union => in_left || in_right,
+			CsgOperation::Intersection => in_left && in_right,
+			CsgOperation::Difference => in_left && !in_right
+		}
+	}
+}
+
+// Boolean combination of two closed objects, built from their spans along the ray
+pub struct Csg {
+	left: Arc<dyn Hittable>,
+	right: Arc<dyn Hittable>,
+	operation: CsgOperation,
+	bbox: Aabb
+}
+
+impl Hittable for Csg {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		for (enter, exit) in self.spans(ray) {
+			if t.surround(enter.t) {
+				return Some(enter);
+			}
+			if t.surround(exit.t) {
+				return Some(exit);
+			}
+		}
+
+		None
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+
+	fn spans(&self, ray: &Ray) -> Vec<(HitRecord<'_>, HitRecord<'_>)> {
+		// (record, comes from the right object, is an entry)
+		let mut events = Vec::new();
+		for (enter, exit) in self.left.spans(ray) {
+			events.push((enter, false, true));
+			events.push((exit, false, false));
+		}
+		for (enter, exit) in self.right.spans(ray) {
+			events.push((enter, true, true));
+			events.push((exit, true, false));
+		}
+		events.sort_by(|a, b| a.0.t.total_cmp(&b.0.t));
+
+		let mut spans = Vec::new();
+		let mut in_left = false;
+		let mut in_right = false;
+		let mut entry = None;
+		for (mut record, from_right, is_entry) in events {
+			let was_inside = self.operation.inside(in_left, in_right);
+			if from_right {
+				in_right = is_entry;
+			}
+			else {
+				in_left = is_entry;
+			}
+			let inside = self.operation.inside(in_left, in_right);
+			if inside == was_inside {
+				continue;
+			}
+
+			// The carved object's surface faces inward once it bounds the result
+			if from_right && self.operation == CsgOperation::Difference {
+				let outward_normal = if record.front_face { record.normal } else { -record.normal };
+				record.set_face_normal(ray, &-outward_normal);
+			}
+
+			if inside {
+				entry = Some(record);
+			}
+			else if let Some(enter) = entry.take() {
+				spans.push((enter, record));
+			}
+		}
+
+		spans
+	}
+}
+
+impl Csg {
+	pub fn new(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>, operation: CsgOperation) -> Csg {
+		let bbox = match operation {
+			CsgOperation::Union => Aabb::surrounding(&left.bounding_box(), &right.bounding_box()),
+			CsgOperation::Intersection | CsgOperation::Difference => left.bounding_box()
+		};
+
+		Csg {
+			left,
+			right,
+			operation,
+			bbox
+		}
+	}
+
+	pub fn union(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Csg {
+		Csg::new(left, right, CsgOperation::Union)
+	}
+
+	pub fn intersection(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Csg {
+		Csg::new(left, right, CsgOperation::Intersection)
+	}
+
+	pub fn difference(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Csg {
+		Csg::new(left, right, CsgOperation::Difference)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::csg::Csg;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::sphere::Sphere;
+	use crate::vec3::{Vec3, Point3};
+
+	fn spheres() -> (Arc<dyn Hittable>, Arc<dyn Hittable>) {
+		let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+		let left = Arc::new(Sphere::new(Point3::new(0., 0., 0.), 1., material.clone()));
+		let right = Arc::new(Sphere::new(Point3::new(1., 0., 0.), 1., material));
+
+		(left, right)
+	}
+
+	fn ray() -> Ray {
+		Ray::new(Point3::new(-5., 0., 0.), Vec3::new(1., 0., 0.))
+	}
+
+	#[test]
+	fn union() {
+		let (left, right) = spheres();
+		let csg = Csg::union(left, right);
+
+		let spans = csg.spans(&ray());
+
+		assert_eq!(spans.len(), 1);
+		assert!(f64::abs(spans[0].0.t - 4.) < 1e-9);
+		assert!(f64::abs(spans[0].1.t - 7.) < 1e-9);
+	}
+
+	#[test]
+	fn intersection() {
+		let (left, right) = spheres();
+		let csg = Csg::intersection(left, right);
+
+		let record = csg.hit(&ray(), &Interval::new(0.001, f64::MAX)).unwrap();
+
+		assert!(f64::abs(record.t - 5.) < 1e-9);
+	}
+
+	#[test]
+	fn difference() {
+		let (left, right) = spheres();
+		let csg = Csg::difference(right, left);
+
+		let record = csg.hit(&ray(), &Interval::new(0.001, f64::MAX)).unwrap();
+
+		assert!(f64::abs(record.t - 6.) < 1e-9);
+		assert!(record.front_face);
+		assert!(record.normal.x() < 0.);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hittable_list::HittableList;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::quad::Quad;
+
+// Axis aligned box made of six quads sharing the same material
+pub struct Cuboid {
+	sides: HittableList
+}
+
+impl Hittable for Cuboid {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		self.sides.hit(ray, t)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.sides.bounding_box()
+	}
+
+	fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+		self.sides.pdf_value(origin, direction)
+	}
+
+	fn random(&self, origin: &Point3) -> Vec3 {
+		self.sides.random(origin)
+	}
+}
+
+impl Cuboid {
+	pub fn new(a: Point3, b: Point3, material: Arc<dyn Material>) -> Cuboid {
+		let min = Point3::new(f64::min(a.x(), b.x()), f64::min(a.y(), b.y()), f64::min(a.z(), b.z()));
+		let max = Point3::new(f64::max(a.x(), b.x()), f64::max(a.y(), b.y()), f64::max(a.z(), b.z()));
+
+		let dx = Vec3::new(max.x() - min.x(), 0., 0.);
+		let dy = Vec3::new(0., max.y() - min.y(), 0.);
+		let dz = Vec3::new(0., 0., max.z() - min.z());
+
+		let mut sides = HittableList::new();
+		sides.push(Box::new(Quad::new(Point3::new(min.x(), min.y(), max.z()), dx, dy, material.clone())));
+		sides.push(Box::new(Quad::new(Point3::new(max.x(), min.y(), max.z()), -dz, dy, material.clone())));
+		sides.push(Box::new(Quad::new(Point3::new(max.x(), min.y(), min.z()), -dx, dy, material.clone())));
+		sides.push(Box::new(Quad::new(Point3::new(min.x(), min.y(), min.z()), dz, dy, material.clone())));
+		sides.push(Box::new(Quad::new(Point3::new(min.x(), max.y(), max.z()), dx, -dz, material.clone())));
+		sides.push(Box::new(Quad::new(Point3::new(min.x(), min.y(), min.z()), dx, dz, material)));
+
+		Cuboid {
+			sides
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::cuboid::Cuboid;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn cuboid() -> Cuboid {
+		let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+
+		Cuboid::new(Point3::new(1., 1., 1.), Point3::new(0., 0., 0.), material)
+	}
+
+	#[test]
+	fn hit() {
+		let cuboid = cuboid();
+		let t = Interval::new(0.001, f64::MAX);
+
+		let record = cuboid.hit(&Ray::new(Point3::new(-5., 0.5, 0.5), Vec3::new(1., 0., 0.)), &t).unwrap();
+		assert!(f64::abs(record.t - 5.) < 1e-9);
+		assert!(record.front_face);
+		assert!((record.normal - Vec3::new(-1., 0., 0.)).length() < 1e-9);
+
+		let inside = cuboid.hit(&Ray::new(Point3::new(0.5, 0.5, 0.5), Vec3::new(0., 1., 0.)), &t).unwrap();
+		assert!(f64::abs(inside.t - 0.5) < 1e-9);
+		assert!(!inside.front_face);
+		assert!((inside.normal - Vec3::new(0., -1., 0.)).length() < 1e-9);
+	}
+
+	#[test]
+	fn spans() {
+		let cuboid = cuboid();
+		let spans = cuboid.spans(&Ray::new(Point3::new(0.5, 0.5, 5.), Vec3::new(0., 0., -1.)));
+
+		assert_eq!(spans.len(), 1);
+		assert!(f64::abs(spans[0].0.t - 4.) < 1e-9);
+		assert!(f64::abs(spans[0].1.t - 5.) < 1e-9);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::polynomial::solve_quadratic;
+
+// Cylinder around the y axis, from `center` up to `center + height`, swept by `phi_max` around the axis
+pub struct Cylinder {
+	center: Point3,
+	radius: f64,
+	height: f64,
+	phi_max: f64,
+	capped: bool,
+	material: Arc<dyn Material>
+}
+
+impl Hittable for Cylinder {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let o = ray.origin() - self.center;
+		let d = ray.direction();
+
+		// (t, outward normal, u, v, dp/dv) of the closest valid hit
+		let mut closest: Option<(f64, Vec3, f64, f64, Vec3)> = None;
+		let mut consider = |root: f64, normal: Vec3, u: f64, v: f64, dpdv: Vec3| {
+			if t.surround(root) && closest.is_none_or(|(best, ..)| root < best) {
+				closest = Some((root, normal, u, v, dpdv));
+			}
+		};
+
+		let a = d.x() * d.x() + d.z() * d.z();
+		let b = 2. * (o.x() * d.x() + o.z() * d.z());
+		let c = o.x() * o.x() + o.z() * o.z() - self.radius * self.radius;
+		for root in solve_quadratic(a, b, c) {
+			let p = o + root * d;
+			let phi = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI);
+			if p.y() < 0. || p.y() > self.height || phi > self.phi_max {
+				continue;
+			}
+
+			let normal = Vec3::new(p.x(), 0., p.z()) / self.radius;
+			consider(root, normal, phi / self.phi_max, p.y() / self.height, Vec3::new(0., self.height, 0.));
+		}
+
+		if self.capped && f64::abs(d.y()) > 1e-8 {
+			for (y, normal) in [(0., Vec3::new(0., -1., 0.)), (self.height, Vec3::new(0., 1., 0.))] {
+				let root = (y - o.y()) / d.y();
+				let p = o + root * d;
+				let r = f64::sqrt(p.x() * p.x() + p.z() * p.z());
+				let phi = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI);
+				if r > self.radius || phi > self.phi_max {
+					continue;
+				}
+
+				let radial = Vec3::new(f64::cos(phi), 0., f64::sin(phi));
+				consider(root, normal, phi / self.phi_max, r / self.radius, self.radius * radial);
+			}
+		}
+
+		let (root, outward_normal, u, v, dpdv) = closest?;
+		let p = o + root * d;
+
+		let mut record = HitRecord::new(ray.at(root), root, self.material.as_ref());
+		record.set_face_normal(ray, &outward_normal);
+		record.u = u;
+		record.v = v;
+		record.dpdu = self.phi_max * Vec3::new(-p.z(), 0., p.x());
+		record.dpdv = dpdv;
+
+		Some(record)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		let extent = Vec3::new(self.radius, 0., self.radius);
+
+		Aabb::from_points(self.center - extent, self.center + extent + Vec3::new(0., self.height, 0.))
+	}
+}
+
+impl Cylinder {
+	pub fn new(center: Point3, radius: f64, height: f64, material: Arc<dyn Material>) -> Cylinder {
+		Cylinder {
+			center,
+			radius,
+			height,
+			phi_max: 2. * PI,
+			capped: true,
+			material
+		}
+	}
+
+	pub fn phi_max(mut self, degrees: f64) -> Cylinder {
+		self.phi_max = f64::clamp(degrees, 0., 360.).to_radians();
+		self
+	}
+
+	pub fn capped(mut self, capped: bool) -> Cylinder {
+		self.capped = capped;
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::cylinder::Cylinder;
+	use crate::hit_assertions::{material, hit, assert_hit};
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn side_and_caps() {
+		let cylinder = Cylinder::new(Point3::new(0., 0., 0.), 1., 2., material());
+
+		assert_hit(hit(&cylinder, Point3::new(-5., 1., 0.), Vec3::new(1., 0., 0.)), 4., Vec3::new(-1., 0., 0.), true);
+		assert_hit(hit(&cylinder, Point3::new(0.5, 5., 0.), Vec3::new(0., -1., 0.)), 3., Vec3::new(0., 1., 0.), true);
+
+		let record = cylinder.hit(&Ray::new(Point3::new(-5., 1., 0.), Vec3::new(1., 0., 0.)), &Interval::new(0.001, f64::MAX)).unwrap();
+		assert!(f64::abs(record.u - 0.5) < 1e-9);
+		assert!(f64::abs(record.v - 0.5) < 1e-9);
+
+		let open = Cylinder::new(Point3::new(0., 0., 0.), 1., 2., material()).capped(false);
+		assert!(hit(&open, Point3::new(0.5, 5., 0.), Vec3::new(0., -1., 0.)).is_none());
+	}
+
+	#[test]
+	fn partial_sweep() {
+		let half = Cylinder::new(Point3::new(0., 0., 0.), 1., 2., material()).phi_max(180.).capped(false);
+
+		// The near side is outside of the sweep, the ray goes through and hits the far one from inside
+		assert_hit(hit(&half, Point3::new(0., 1., -5.), Vec3::new(0., 0., 1.)), 6., Vec3::new(0., 0., -1.), false);
+	}
+}
\ No newline at end of file
@@ -2,39 +2,235 @@ use crate::material::Material;
 use crate::ray::Ray;
 use crate::hit_record::HitRecord;
 use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::medium_stack::Medium;
+use crate::dispersion::Dispersion;
+use crate::thin_film::ThinFilm;
+use crate::microfacet::Ggx;
+use crate::onb::Onb;
+use crate::fresnel;
 
 use rand::Rng;
 
 pub struct Dielectric {
 	refraction_coeff: f64,
+	dispersion: Option<Dispersion>,
+	thin_film: Option<ThinFilm>,
+	distribution: Ggx,
+	absorption: Color,
+	scattering: Color,
+	anisotropy: f64,
+	priority: u32
 }
 
 impl Material for Dielectric {
 	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
-		let refraction_ratio = if rec.front_face { 1. / self.refraction_coeff } else { self.refraction_coeff };
-		
+		let mut media = ray.media();
+		let entering = rec.front_face;
+
+		// Crossing into or out of a volume dominated by a higher priority one is not a real interface. The stack
+		// tracks a few nested volumes, see `MediumStack::push`
+		if media.overrides(self.id(), self.priority) {
+			if entering {
+				media.push(self.medium());
+			}
+			else {
+				media.remove(self.id());
+			}
+
+			return Some((ray.spawn(rec.point, ray.direction()).with_media(media), Color::new(1., 1., 1.)));
+		}
+
+		// With a dispersive medium on either side, or a thin film, the hero wavelength is followed alone
+		let outside = media.current_without(self.id());
+		let dispersive = self.dispersion.is_some() || outside.is_some_and(|medium| medium.dispersion.is_some());
+		let wavelengths = ray.wavelengths()
+			.filter(|_| dispersive || self.thin_film.is_some())
+			.map(|wavelengths| wavelengths.collapse());
+		let lambda = wavelengths.map(|wavelengths| wavelengths.hero());
+
+		let inside_ior = self.medium().ior_at(lambda);
+		let outside_ior = outside.map_or(1., |medium| medium.ior_at(lambda));
+		let (incident_ior, transmitted_ior) = if entering {
+			(media.current().map_or(1., |medium| medium.ior_at(lambda)), inside_ior)
+		}
+		else if media.contains(self.id()) {
+			(inside_ior, outside_ior)
+		}
+		else {
+			(inside_ior, 1.)
+		};
+		let refraction_ratio = incident_ior / transmitted_ior;
+
+		let substrate = Color::new(transmitted_ior, transmitted_ior, transmitted_ior);
+		let film = |cos_theta: f64| {
+			self.thin_film.as_ref().map(|film| film.reflectance(ray, rec, cos_theta, incident_ior, &substrate, &Color::new(0., 0., 0.)))
+		};
+
 		let unit_direction = ray.direction().unit();
-		let cos_theta = f64::min((-unit_direction).dot(rec.normal), 1.0);
-		let sin_theta = f64::sqrt(1. - cos_theta * cos_theta);
+		let (direction, refracted, weight) = if self.distribution.is_smooth() {
+			Dielectric::scatter_smooth(&unit_direction, &rec.normal, refraction_ratio, film)
+		}
+		else {
+			self.scatter_rough(&unit_direction, &rec.normal, refraction_ratio, film)?
+		};
 
-		let cannot_refract = (refraction_ratio * sin_theta) > 1.0;
-		if cannot_refract || Dielectric::reflectance(cos_theta, self.refraction_coeff) > rand::thread_rng().gen() {
-			let reflected = unit_direction.reflect(&rec.normal);
+		if refracted {
+			if entering {
+				media.push(self.medium());
+			}
+			else {
+				media.remove(self.id());
+			}
+		}
 
-			return Some((Ray::new(rec.point, reflected), Color::new(1., 1., 1.)));
+		let mut scattered = ray.spawn(rec.point, direction).with_media(media);
+		if let Some(wavelengths) = wavelengths {
+			scattered = scattered.with_wavelengths(wavelengths);
 		}
-		
-		let refracted = unit_direction.refract(&rec.normal, refraction_ratio);
 
-		Some((Ray::new(rec.point, refracted), Color::new(1., 1., 1.)))
+		Some((scattered, weight))
 	}
 }
 
 impl Dielectric {
 	pub fn new(refraction_coeff: f64) -> Dielectric {
-		Dielectric { 
-			refraction_coeff
+		Dielectric {
+			refraction_coeff,
+			dispersion: None,
+			thin_film: None,
+			distribution: Ggx::new(0., 0.),
+			absorption: Color::new(0., 0., 0.),
+			scattering: Color::new(0., 0., 0.),
+			anisotropy: 0.,
+			priority: 0
+		}
+	}
+
+	// GGX microfacet roughness, transmission follows Walter et al. 2007
+	pub fn roughness(mut self, roughness: f64) -> Dielectric {
+		self.distribution = Ggx::from_roughness(roughness, roughness);
+		self
+	}
+
+	// Wavelength dependent IOR used in spectral mode, RGB rendering takes the d-line index
+	pub fn dispersion(mut self, dispersion: Dispersion) -> Dielectric {
+		self.refraction_coeff = dispersion.ior_d();
+		self.dispersion = Some(dispersion);
+		self
+	}
+
+	// Interference coating on the outside of the surface
+	pub fn thin_film(mut self, thin_film: ThinFilm) -> Dielectric {
+		self.thin_film = Some(thin_film);
+		self
+	}
+
+	// Beer-Lambert absorption coefficient per unit length travelled inside
+	pub fn absorption(mut self, absorption: Color) -> Dielectric {
+		self.absorption = absorption;
+		self
+	}
+
+	// Scattering coefficient and Henyey-Greenstein anisotropy of the interior, turning it into a
+	// participating medium the path random walks through
+	pub fn scattering(mut self, scattering: Color, anisotropy: f64) -> Dielectric {
+		self.scattering = scattering;
+		self.anisotropy = f64::clamp(anisotropy, -0.99, 0.99);
+		self
+	}
+
+	// Where dielectrics overlap, e.g. liquid slightly inside its glass, the highest priority one is used
+	pub fn priority(mut self, priority: u32) -> Dielectric {
+		self.priority = priority;
+		self
+	}
+
+	// Media are told apart by the address of their material, which the scene shares for as long as it renders
+	fn id(&self) -> usize {
+		self as *const Dielectric as usize
+	}
+
+	fn medium(&self) -> Medium {
+		Medium {
+			id: self.id(),
+			priority: self.priority,
+			ior: self.refraction_coeff,
+			dispersion: self.dispersion,
+			absorption: self.absorption,
+			scattering: self.scattering,
+			anisotropy: self.anisotropy
+		}
+	}
+
+	// `film` overrides the reflectance, possibly per channel, when the surface is coated
+	fn scatter_smooth<F>(unit_direction: &Vec3, normal: &Vec3, refraction_ratio: f64, film: F) -> (Vec3, bool, Color)
+	where
+		F: Fn(f64) -> Option<Color>
+	{
+		let cos_theta = f64::min((-*unit_direction).dot(*normal), 1.0);
+		let sin_theta = f64::sqrt(1. - cos_theta * cos_theta);
+
+		let cannot_refract = (refraction_ratio * sin_theta) > 1.0;
+		if cannot_refract {
+			return (unit_direction.reflect(normal), false, Color::new(1., 1., 1.));
+		}
+
+		let reflectance = film(cos_theta).unwrap_or_else(|| {
+			let reflectance = Dielectric::reflectance(cos_theta, refraction_ratio);
+			Color::new(reflectance, reflectance, reflectance)
+		});
+		let (reflected, weight) = Dielectric::choose(&reflectance);
+		if reflected {
+			return (unit_direction.reflect(normal), false, weight);
 		}
+
+		(unit_direction.refract(normal, refraction_ratio), true, weight)
+	}
+
+	// Picks reflection or transmission through a visible microfacet with probability F,
+	// which leaves G2 / G1 as the weight of both events
+	fn scatter_rough<F>(&self, unit_direction: &Vec3, normal: &Vec3, refraction_ratio: f64, film: F) -> Option<(Vec3, bool, Color)>
+	where
+		F: Fn(f64) -> Option<Color>
+	{
+		let onb = Onb::new(normal);
+		let wo = onb.to_local(&-*unit_direction);
+		if wo.z() <= 0. {
+			return None;
+		}
+
+		let mut rng = rand::thread_rng();
+		let m = self.distribution.sample_visible_normal(&wo, rng.gen(), rng.gen());
+		let reflectance = film(wo.dot(m)).unwrap_or_else(|| {
+			let reflectance = fresnel::dielectric(wo.dot(m), 1. / refraction_ratio);
+			Color::new(reflectance, reflectance, reflectance)
+		});
+
+		let (reflected, fresnel_weight) = Dielectric::choose(&reflectance);
+		let (wi, refracted) = if reflected {
+			(2. * wo.dot(m) * m - wo, false)
+		}
+		else {
+			((-wo).refract(&m, refraction_ratio), true)
+		};
+		if (wi.z() > 0.) == refracted {
+			return None;
+		}
+
+		let weight = self.distribution.g2(&wo, &wi) / self.distribution.g1(&wo);
+
+		Some((onb.local(&wi), refracted, weight * fresnel_weight))
+	}
+
+	// Reflects with the average reflectance as probability, coloured reflectances leave a tint on both events
+	fn choose(reflectance: &Color) -> (bool, Color) {
+		let probability = (reflectance.x() + reflectance.y() + reflectance.z()) / 3.;
+		if rand::thread_rng().gen::<f64>() < probability {
+			return (true, *reflectance / probability);
+		}
+
+		(false, (Color::new(1., 1., 1.) - *reflectance) / (1. - probability))
 	}
 
 	fn reflectance(cosine: f64, refraction_coeff: f64) -> f64 {
@@ -43,4 +239,51 @@ impl Dielectric {
 
 		r0 + (1. - r0) * f64::powf(1. - cosine, 5.)
 	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dielectric::Dielectric;
+	use crate::material::Material;
+	use crate::medium_stack::MediumStack;
+	use crate::hit_record::HitRecord;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	// A ray inside both the glass and the liquid filling it, leaving through `surface`
+	fn leave(surface: &Dielectric, glass: &Dielectric, liquid: &Dielectric, direction: Vec3) -> (Ray, Vec3) {
+		let mut media = MediumStack::new();
+		media.push(glass.medium());
+		media.push(liquid.medium());
+
+		let ray = Ray::new(Point3::new(0., 0., 1.), direction).with_media(media);
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., surface);
+		record.normal = Vec3::new(0., 0., 1.);
+		record.front_face = false;
+
+		surface.scatter(&ray, &record).unwrap()
+	}
+
+	#[test]
+	fn liquid_in_glass() {
+		let glass = Dielectric::new(1.5).priority(1);
+		let liquid = Dielectric::new(1.33).priority(2);
+		let direction = Vec3::new(0.5, 0., -1.).unit();
+
+		// The glass wall overlapping the liquid is a false interface, crossed straight and unweighted
+		let (scattered, attenuation) = leave(&glass, &glass, &liquid, direction);
+		assert!((scattered.direction() - direction).length() < 1e-12);
+		assert!((attenuation - Vec3::new(1., 1., 1.)).length() < 1e-12);
+		assert!(!scattered.media().contains(glass.id()));
+		assert_eq!(scattered.media().current().unwrap().id, liquid.id());
+
+		// Leaving the liquid refracts from its IOR into the glass around it
+		let refracted = (0..100)
+			.map(|_| leave(&liquid, &glass, &liquid, direction).0)
+			.find(|scattered| scattered.direction().z() < 0.)
+			.unwrap();
+		assert_eq!(refracted.media().current().unwrap().id, glass.id());
+		let sin_out = refracted.direction().unit().x();
+		assert!(f64::abs(sin_out - direction.x() * 1.33 / 1.5) < 1e-9, "{}", sin_out);
+	}
 }
\ No newline at end of file
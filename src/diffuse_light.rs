@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+
+// Emits light on both sides of the surface unless made one sided, and never scatters
+pub struct DiffuseLight {
+	emit: Arc<dyn Texture>,
+	two_sided: bool
+}
+
+impl Material for DiffuseLight {
+	fn scatter(&self, _: &Ray, _: &HitRecord) -> Option<(Ray, Color)> {
+		None
+	}
+
+	fn emitted(&self, rec: &HitRecord) -> Color {
+		if !rec.front_face && !self.two_sided {
+			return Color::new(0., 0., 0.);
+		}
+
+		self.emit.value(rec.u, rec.v, &rec.point)
+	}
+}
+
+impl DiffuseLight {
+	pub fn new(emit: Color) -> DiffuseLight {
+		DiffuseLight::from_texture(Arc::new(SolidColor::new(emit)))
+	}
+
+	pub fn from_texture(emit: Arc<dyn Texture>) -> DiffuseLight {
+		DiffuseLight {
+			emit,
+			two_sided: true
+		}
+	}
+
+	// Only the side the geometric normal points to emits
+	pub fn two_sided(mut self, two_sided: bool) -> DiffuseLight {
+		self.two_sided = two_sided;
+		self
+	}
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+use crate::onb::Onb;
+
+use rand::Rng;
+
+// Thin sheet like a leaf or paper, scattering diffusely on both sides of the surface
+pub struct DiffuseTransmission {
+	reflectance: Arc<dyn Texture>,
+	transmittance: Arc<dyn Texture>
+}
+
+impl Material for DiffuseTransmission {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let reflectance = self.reflectance.value(rec.u, rec.v, &rec.point);
+		let transmittance = self.transmittance.value(rec.u, rec.v, &rec.point);
+
+		// The side is chosen proportionally to the average of each color
+		let reflect = average(&reflectance);
+		let transmit = average(&transmittance);
+		if reflect + transmit <= 0. {
+			return None;
+		}
+
+		let probability = reflect / (reflect + transmit);
+		let (normal, weight) = if rand::thread_rng().gen::<f64>() < probability {
+			(rec.normal, reflectance / probability)
+		}
+		else {
+			(-rec.normal, transmittance / (1. - probability))
+		};
+
+		let direction = Onb::new(&normal).local(&Vec3::random_cosine_direction());
+
+		Some((ray.spawn(rec.point, direction), weight))
+	}
+}
+
+impl DiffuseTransmission {
+	pub fn new(reflectance: Color, transmittance: Color) -> DiffuseTransmission {
+		DiffuseTransmission::from_textures(Arc::new(SolidColor::new(reflectance)), Arc::new(SolidColor::new(transmittance)))
+	}
+
+	pub fn from_textures(reflectance: Arc<dyn Texture>, transmittance: Arc<dyn Texture>) -> DiffuseTransmission {
+		DiffuseTransmission {
+			reflectance,
+			transmittance
+		}
+	}
+}
+
+fn average(color: &Color) -> f64 {
+	(color.x() + color.y() + color.z()) / 3.
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::diffuse_transmission::DiffuseTransmission;
+	use crate::material::Material;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn transmitted_fraction() {
+		let sheet = DiffuseTransmission::new(Color::new(0.2, 0.2, 0.2), Color::new(0.6, 0.6, 0.6));
+		let ray = Ray::new(Point3::new(0., 0., 1.), Vec3::new(0., 0., -1.));
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., &sheet);
+		record.normal = Vec3::new(0., 0., 1.);
+		record.front_face = true;
+
+		let samples = 20000;
+		let (mut transmitted, mut energy) = (0, 0.);
+		for _ in 0..samples {
+			let (scattered, attenuation) = sheet.scatter(&ray, &record).unwrap();
+			if scattered.direction().z() < 0. {
+				transmitted += 1;
+				energy += attenuation.y();
+			}
+		}
+
+		// Sides are picked in proportion, and the weights carry the transmittance through on average
+		assert!(f64::abs(transmitted as f64 / samples as f64 - 0.75) < 0.015);
+		assert!(f64::abs(energy / samples as f64 - 0.6) < 0.015);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+
+use rand::Rng;
+
+// u is the angle around the normal, v the distance to the center divided by the radius
+pub struct Disk {
+	center: Point3,
+	normal: Vec3,
+	radius: f64,
+	tangent: Vec3,
+	bitangent: Vec3,
+	material: Arc<dyn Material>,
+	bbox: Aabb
+}
+
+impl Hittable for Disk {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let denom = self.normal.dot(ray.direction());
+		if f64::abs(denom) < 1e-8 {
+			return None;
+		}
+
+		let root = self.normal.dot(self.center - ray.origin()) / denom;
+		if !t.surround(root) {
+			return None;
+		}
+
+		let hit_point = ray.at(root);
+		let planar_hitpt = hit_point - self.center;
+		let distance_squared = planar_hitpt.squared_length();
+		if distance_squared > self.radius * self.radius {
+			return None;
+		}
+
+		let phi = f64::atan2(self.bitangent.dot(planar_hitpt), self.tangent.dot(planar_hitpt));
+
+		let mut record = HitRecord::new(hit_point, root, self.material.as_ref());
+		record.set_face_normal(ray, &self.normal);
+		record.u = phi.rem_euclid(2. * PI) / (2. * PI);
+		record.v = f64::sqrt(distance_squared) / self.radius;
+		record.dpdu = 2. * PI * (self.tangent.dot(planar_hitpt) * self.bitangent - self.bitangent.dot(planar_hitpt) * self.tangent);
+		record.dpdv = self.radius * (f64::cos(phi) * self.tangent + f64::sin(phi) * self.bitangent);
+
+		Some(record)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+
+	fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+		let Some(record) = self.hit(&Ray::new(*origin, *direction), &Interval::new(0.001, f64::MAX)) else {
+			return 0.;
+		};
+
+		let distance_squared = record.t * record.t * direction.squared_length();
+		let cosine = f64::abs(direction.dot(record.normal) / direction.length());
+		let area = PI * self.radius * self.radius;
+
+		distance_squared / (cosine * area)
+	}
+
+	fn random(&self, origin: &Point3) -> Vec3 {
+		let mut rng = rand::thread_rng();
+		let r = self.radius * f64::sqrt(rng.gen::<f64>());
+		let phi = 2. * PI * rng.gen::<f64>();
+		let p = self.center + r * f64::cos(phi) * self.tangent + r * f64::sin(phi) * self.bitangent;
+
+		p - *origin
+	}
+}
+
+impl Disk {
+	pub fn new(center: Point3, normal: Vec3, radius: f64, material: Arc<dyn Material>) -> Disk {
+		let normal = normal.unit();
+		let helper = if f64::abs(normal.x()) > 0.9 { Vec3::new(0., 1., 0.) } else { Vec3::new(1., 0., 0.) };
+		let tangent = helper.cross(normal).unit();
+
+		let extent = Vec3::new(
+			radius * f64::sqrt(f64::max(1. - normal.x() * normal.x(), 0.)),
+			radius * f64::sqrt(f64::max(1. - normal.y() * normal.y(), 0.)),
+			radius * f64::sqrt(f64::max(1. - normal.z() * normal.z(), 0.))
+		);
+
+		Disk {
+			center,
+			normal,
+			radius,
+			tangent,
+			bitangent: normal.cross(tangent),
+			material,
+			bbox: Aabb::from_points(center - extent, center + extent).pad()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::f64::consts::PI;
+
+	use crate::disk::Disk;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn disk() -> Disk {
+		let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+
+		Disk::new(Point3::new(0., 0., 0.), Vec3::new(0., 0., 1.), 1., material)
+	}
+
+	#[test]
+	fn hit() {
+		let disk = disk();
+		let t = Interval::new(0.001, f64::MAX);
+
+		let record = disk.hit(&Ray::new(Point3::new(0.5, 0., 2.), Vec3::new(0., 0., -1.)), &t).unwrap();
+		assert!(f64::abs(record.t - 2.) < 1e-9);
+		assert!(f64::abs(record.v - 0.5) < 1e-9);
+		assert!((record.normal - Vec3::new(0., 0., 1.)).length() < 1e-9);
+
+		// A quarter turn further around the normal
+		let turned = disk.hit(&Ray::new(Point3::new(0., 0.5, 2.), Vec3::new(0., 0., -1.)), &t).unwrap();
+		assert!(f64::abs((turned.u - record.u).rem_euclid(1.) - 0.25) < 1e-9);
+
+		assert!(disk.hit(&Ray::new(Point3::new(0.8, 0.8, 2.), Vec3::new(0., 0., -1.)), &t).is_none());
+	}
+
+	#[test]
+	fn area_sampling() {
+		let disk = disk();
+		let origin = Point3::new(0., 0., 3.);
+
+		assert!(f64::abs(disk.pdf_value(&origin, &Vec3::new(0., 0., -1.)) - 9. / PI) < 1e-9);
+		for _ in 0..100 {
+			assert!(disk.pdf_value(&origin, &disk.random(&origin)) > 0.);
+		}
+	}
+}
\ No newline at end of file
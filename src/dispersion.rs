@@ -0,0 +1,73 @@
+// Wavelength dependent index of refraction, wavelengths in nanometers and coefficients in micrometers
+#[derive(Clone, Copy, Debug)]
+pub enum Dispersion {
+	Cauchy { a: f64, b: f64 },
+	Sellmeier { b: [f64; 3], c: [f64; 3] }
+}
+
+impl Dispersion {
+	// n = A + B / λ²
+	pub fn cauchy(a: f64, b: f64) -> Dispersion {
+		Dispersion::Cauchy { a, b }
+	}
+
+	// n² = 1 + Σ Bᵢ λ² / (λ² - Cᵢ)
+	pub fn sellmeier(b: [f64; 3], c: [f64; 3]) -> Dispersion {
+		Dispersion::Sellmeier { b, c }
+	}
+
+	pub fn bk7() -> Dispersion {
+		Dispersion::sellmeier([1.03961212, 0.231792344, 1.01046945], [0.00600069867, 0.0200179144, 103.560653])
+	}
+
+	pub fn dense_flint() -> Dispersion {
+		Dispersion::sellmeier([1.73759695, 0.313747346, 1.89878101], [0.013188707, 0.0623068142, 155.23629])
+	}
+
+	pub fn fused_silica() -> Dispersion {
+		Dispersion::sellmeier([0.6961663, 0.4079426, 0.8974794], [0.004679148, 0.01351206, 97.934003])
+	}
+
+	pub fn diamond() -> Dispersion {
+		Dispersion::sellmeier([0.3306, 4.3356, 0.], [0.030625, 0.011236, 0.])
+	}
+
+	pub fn water() -> Dispersion {
+		Dispersion::cauchy(1.3246, 0.003070)
+	}
+
+	pub fn ior(&self, lambda: f64) -> f64 {
+		let micrometers = lambda / 1000.;
+		let squared = micrometers * micrometers;
+
+		match self {
+			Dispersion::Cauchy { a, b } => a + b / squared,
+			Dispersion::Sellmeier { b, c } => {
+				let sum: f64 = (0..3).map(|i| b[i] * squared / (squared - c[i])).sum();
+				f64::sqrt(1. + sum)
+			}
+		}
+	}
+
+	// Index at the sodium d-line, used outside of spectral mode
+	pub fn ior_d(&self) -> f64 {
+		self.ior(587.6)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dispersion::Dispersion;
+
+	#[test]
+	fn bk7() {
+		assert!((Dispersion::bk7().ior_d() - 1.5168).abs() < 1e-4);
+	}
+
+	#[test]
+	fn normal_dispersion() {
+		for dispersion in [Dispersion::bk7(), Dispersion::diamond(), Dispersion::water()] {
+			assert!(dispersion.ior(450.) > dispersion.ior(650.));
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use crate::blackbody;
+use crate::cie;
+use crate::color::Color;
+use crate::vec3::Vec3;
+
+// Film response in photographic units. Scene radiance is read as luminance in cd/m², which the film scales
+// so that the saturation based sensitivity of the ISO standard maps to white
+#[derive(Clone, Copy, Debug)]
+pub struct Exposure {
+	pub iso: f64,
+	// In seconds, the motion blur shutter interval of the camera is in scene time and left alone
+	pub shutter_speed: f64,
+	// Set from the camera's lens or lens system when it has one
+	pub f_number: f64,
+	// Strength of the cos^4 falloff towards the image corners, from 0 to 1. Only perspective cameras without a
+	// lens system have it, the others have no such flat film or trace the falloff themselves
+	pub vignetting: f64,
+	// White of the illuminant the film is balanced for
+	white: Vec3
+}
+
+impl Exposure {
+	// Balanced for daylight and without vignetting
+	pub fn new(iso: f64, shutter_speed: f64, f_number: f64) -> Exposure {
+		Exposure {
+			iso,
+			shutter_speed,
+			f_number,
+			vignetting: 0.,
+			white: cie::D65
+		}
+	}
+
+	// Whites lit by a black body at `temperature` kelvins come out neutral
+	pub fn white_balance(mut self, temperature: f64) -> Exposure {
+		let xyz = blackbody::xyz(temperature);
+		if xyz.y() > 0. {
+			self.white = xyz / xyz.y();
+		}
+		self
+	}
+
+	pub fn vignetting(mut self, vignetting: f64) -> Exposure {
+		self.vignetting = vignetting;
+		self
+	}
+
+	// Exposure value at ISO 100
+	pub fn ev100(&self) -> f64 {
+		f64::log2(self.f_number * self.f_number / self.shutter_speed * 100. / self.iso)
+	}
+
+	// Factor from luminance to film values, the 1.2 is the headroom of the saturation based sensitivity
+	pub fn scale(&self) -> f64 {
+		1. / (1.2 * f64::powf(2., self.ev100()))
+	}
+
+	// Film value of the averaged radiance reaching a pixel `cos_theta` off the optical axis
+	pub fn develop(&self, radiance: &Color, cos_theta: f64) -> Color {
+		let falloff = 1. - self.vignetting * (1. - f64::powi(cos_theta, 4));
+		let balanced = cie::xyz_to_rgb(&cie::adapt(&cie::rgb_to_xyz(radiance), &self.white, &cie::D65));
+
+		self.scale() * falloff * balanced
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::blackbody;
+	use crate::color::Color;
+	use crate::exposure::Exposure;
+
+	#[test]
+	fn sunny_sixteen() {
+		let exposure = Exposure::new(100., 1. / 100., 16.);
+
+		assert!((exposure.ev100() - f64::log2(25600.)).abs() < 1e-12);
+		// Doubling the sensitivity takes a stop off
+		assert!((Exposure::new(200., 1. / 100., 16.).ev100() - exposure.ev100() + 1.).abs() < 1e-12);
+		assert!((Exposure::new(100., 1., 1.).scale() - 1. / 1.2).abs() < 1e-12);
+	}
+
+	#[test]
+	fn white_balance_neutralises_illuminant() {
+		let exposure = Exposure::new(100., 1., 1.).white_balance(3200.);
+		let tungsten = blackbody::color(3200.);
+		assert!(tungsten.x() > 1.5 * tungsten.z());
+
+		let white = exposure.develop(&tungsten, 1.) / exposure.scale();
+		assert!((white.x() - white.y()).abs() < 1e-3 * white.y());
+		assert!((white.z() - white.y()).abs() < 1e-3 * white.y());
+
+		// Daylight balance leaves colours as they are
+		let daylight = Exposure::new(100., 1., 1.);
+		let color = Color::new(0.2, 0.5, 0.8);
+		assert!((daylight.develop(&color, 1.) / daylight.scale() - color).length() < 1e-6);
+	}
+
+	#[test]
+	fn vignetting() {
+		let exposure = Exposure::new(100., 1., 1.).vignetting(1.);
+		let white = Color::new(1., 1., 1.);
+		let corner = exposure.develop(&white, f64::cos(f64::to_radians(30.)));
+
+		assert!((corner.y() / exposure.develop(&white, 1.).y() - 0.5625).abs() < 1e-9);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::f64::consts::PI;
+
+use crate::color::Color;
+
+// Unpolarised reflectance of a dielectric interface, `eta` is the transmitted over incident IOR
+pub fn dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+	let cos_theta_i = f64::clamp(cos_theta_i, 0., 1.);
+	let sin2_theta_t = (1. - cos_theta_i * cos_theta_i) / (eta * eta);
+	if sin2_theta_t >= 1. {
+		return 1.;
+	}
+
+	let cos_theta_t = f64::sqrt(1. - sin2_theta_t);
+	let r_parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+	let r_perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+
+	(r_parallel * r_parallel + r_perpendicular * r_perpendicular) / 2.
+}
+
+// Unpolarised reflectance of a conductor with complex IOR eta + i k, per channel
+pub fn conductor(cos_theta_i: f64, eta: &Color, k: &Color) -> Color {
+	Color::new(
+		conductor_channel(cos_theta_i, eta.x(), k.x()),
+		conductor_channel(cos_theta_i, eta.y(), k.y()),
+		conductor_channel(cos_theta_i, eta.z(), k.z())
+	)
+}
+
+fn conductor_channel(cos_theta_i: f64, eta: f64, k: f64) -> f64 {
+	let cos2 = f64::clamp(cos_theta_i * cos_theta_i, 0., 1.);
+	let sin2 = 1. - cos2;
+
+	let t0 = eta * eta - k * k - sin2;
+	let a2_plus_b2 = f64::sqrt(t0 * t0 + 4. * eta * eta * k * k);
+	let a = f64::sqrt(f64::max(0.5 * (a2_plus_b2 + t0), 0.));
+	let cos = f64::sqrt(cos2);
+
+	let rs = (a2_plus_b2 - 2. * a * cos + cos2) / (a2_plus_b2 + 2. * a * cos + cos2);
+	let t1 = cos2 * a2_plus_b2 + sin2 * sin2;
+	let t2 = 2. * a * cos * sin2;
+	let rp = rs * (t1 - t2) / (t1 + t2);
+
+	(rs + rp) / 2.
+}
+
+// Reflectance of a film of `thickness` nanometers over a substrate eta + i k, summing the
+// interfering reflections with the Airy formula. IORs are relative to the incident medium
+pub fn thin_film(cos_theta_i: f64, lambda: f64, thickness: f64, film_ior: f64, eta: f64, k: f64) -> f64 {
+	let cos1 = f64::clamp(cos_theta_i, 0., 1.);
+	let sin2_1 = 1. - cos1 * cos1;
+
+	let sin2_2 = sin2_1 / (film_ior * film_ior);
+	if sin2_2 >= 1. {
+		return 1.;
+	}
+	let cos2 = f64::sqrt(1. - sin2_2);
+
+	let n1 = Complex::real(1.);
+	let n2 = Complex::real(film_ior);
+	let n3 = Complex::new(eta, k);
+	let c1 = Complex::real(cos1);
+	let c2 = Complex::real(cos2);
+	let c3 = Complex::real(1.).sub(Complex::real(sin2_1).div(n3.mul(n3))).sqrt();
+
+	let phase = 4. * PI * film_ior * thickness * cos2 / lambda;
+	let shift = Complex::new(f64::cos(phase), f64::sin(phase));
+
+	let airy = |r12: Complex, r23: Complex| {
+		let r23 = r23.mul(shift);
+		r12.add(r23).div(Complex::real(1.).add(r12.mul(r23))).norm2()
+	};
+
+	let rs = airy(reflection(n1.mul(c1), n2.mul(c2)), reflection(n2.mul(c2), n3.mul(c3)));
+	let rp = airy(reflection(n2.mul(c1), n1.mul(c2)), reflection(n3.mul(c2), n2.mul(c3)));
+
+	f64::min((rs + rp) / 2., 1.)
+}
+
+fn reflection(a: Complex, b: Complex) -> Complex {
+	a.sub(b).div(a.add(b))
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Complex {
+	re: f64,
+	im: f64
+}
+
+impl Complex {
+	fn new(re: f64, im: f64) -> Complex {
+		Complex {
+			re,
+			im
+		}
+	}
+
+	fn real(re: f64) -> Complex {
+		Complex::new(re, 0.)
+	}
+
+	fn add(self, rhs: Complex) -> Complex {
+		Complex::new(self.re + rhs.re, self.im + rhs.im)
+	}
+
+	fn sub(self, rhs: Complex) -> Complex {
+		Complex::new(self.re - rhs.re, self.im - rhs.im)
+	}
+
+	fn mul(self, rhs: Complex) -> Complex {
+		Complex::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+	}
+
+	fn div(self, rhs: Complex) -> Complex {
+		let norm2 = rhs.norm2();
+		Complex::new((self.re * rhs.re + self.im * rhs.im) / norm2, (self.im * rhs.re - self.re * rhs.im) / norm2)
+	}
+
+	fn norm2(self) -> f64 {
+		self.re * self.re + self.im * self.im
+	}
+
+	// Principal square root
+	fn sqrt(self) -> Complex {
+		let modulus = f64::sqrt(self.norm2());
+		let re = f64::sqrt(f64::max((modulus + self.re) / 2., 0.));
+		let im = f64::sqrt(f64::max((modulus - self.re) / 2., 0.));
+
+		Complex::new(re, if self.im < 0. { -im } else { im })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::fresnel;
+	use crate::color::Color;
+
+	#[test]
+	fn normal_incidence() {
+		assert!(f64::abs(fresnel::dielectric(1., 1.5) - 0.04) < 1e-9);
+	}
+
+	#[test]
+	fn total_internal_reflection() {
+		assert_eq!(fresnel::dielectric(0.1, 1. / 1.5), 1.);
+	}
+
+	#[test]
+	fn conductor_without_absorption() {
+		let eta = Color::new(1.5, 1.5, 1.5);
+		let k = Color::new(0., 0., 0.);
+
+		for cos in [1., 0.7, 0.3] {
+			let reflectance = fresnel::conductor(cos, &eta, &k);
+			assert!(f64::abs(reflectance.x() - fresnel::dielectric(cos, 1.5)) < 1e-9);
+		}
+	}
+
+	#[test]
+	fn thin_film_without_thickness() {
+		for cos in [1., 0.7, 0.3] {
+			let dielectric = fresnel::thin_film(cos, 550., 0., 1.33, 1.5, 0.);
+			assert!(f64::abs(dielectric - fresnel::dielectric(cos, 1.5)) < 1e-9);
+
+			let gold = fresnel::thin_film(cos, 550., 0., 1.33, 0.375, 2.386);
+			let expected = fresnel::conductor(cos, &Color::new(0.375, 0.375, 0.375), &Color::new(2.386, 2.386, 2.386));
+			assert!(f64::abs(gold - expected.x()) < 1e-9);
+		}
+	}
+
+	#[test]
+	fn quarter_wave_coating() {
+		// A film of IOR sqrt(1.5) a quarter wavelength thick cancels the reflection of glass
+		let film_ior = f64::sqrt(1.5);
+		let reflectance = fresnel::thin_film(1., 550., 550. / (4. * film_ior), film_ior, 1.5, 0.);
+
+		assert!(reflectance < 1e-9);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::color::Color;
+use crate::transform::Transform;
+use crate::voxel_grid::VoxelGrid;
+use crate::isotropic::Isotropic;
+use crate::henyey_greenstein::HenyeyGreenstein;
+use crate::blackbody;
+
+use rand::Rng;
+
+const EMISSION_TABLE_SIZE: usize = 256;
+
+// Heterogeneous medium sampled from a density grid mapped on the unit cube by `transform`,
+// collisions are found by delta tracking against the grid's maximum density
+pub struct GridVolume {
+	density: Arc<VoxelGrid>,
+	density_scale: f64,
+	transform: Transform,
+	bbox: Aabb,
+	material: GridVolumeMaterial
+}
+
+// Scatters with the phase function and emits from the temperature grid at real collisions
+struct GridVolumeMaterial {
+	phase_function: Arc<dyn Material>,
+	albedo: Color,
+	to_grid: Transform,
+	temperature: Option<Arc<VoxelGrid>>,
+	emission_scale: f64,
+	emission_table: Vec<Color>
+}
+
+impl Hittable for GridVolume {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let majorant = self.majorant();
+		if majorant <= 0. {
+			return None;
+		}
+
+		let grid_ray = self.transform.inverse().ray(ray);
+		let range = unit_cube().clip(&grid_ray, t)?;
+		let length = ray.direction().length();
+
+		let mut rng = rand::thread_rng();
+		let mut root = range.min;
+		loop {
+			root -= f64::ln(1. - rng.gen::<f64>()) / (majorant * length);
+			if root >= range.max {
+				return None;
+			}
+
+			// Null collisions keep going, real ones happen with probability density / majorant
+			let density = self.density.lookup(&grid_ray.at(root)) * self.density_scale;
+			if rng.gen::<f64>() * majorant < density {
+				let mut record = HitRecord::new(ray.at(root), root, &self.material);
+				record.normal = Vec3::new(1., 0., 0.);
+				record.front_face = true;
+
+				return Some(record);
+			}
+		}
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+}
+
+impl GridVolume {
+	pub fn new(density: Arc<VoxelGrid>, transform: Transform, density_scale: f64, albedo: Color) -> GridVolume {
+		GridVolume {
+			bbox: transform.bounding_box(&unit_cube()),
+			material: GridVolumeMaterial {
+				phase_function: Arc::new(Isotropic::new(albedo)),
+				albedo,
+				to_grid: transform.inverse(),
+				temperature: None,
+				emission_scale: 0.,
+				emission_table: Vec::new()
+			},
+			density,
+			density_scale,
+			transform
+		}
+	}
+
+	pub fn anisotropy(mut self, g: f64) -> GridVolume {
+		self.material.phase_function = Arc::new(HenyeyGreenstein::new(self.material.albedo, g));
+		self
+	}
+
+	// Temperatures in Kelvin, emission is black body radiance relative to 6500K times `emission_scale`
+	pub fn temperature(mut self, temperature: Arc<VoxelGrid>, emission_scale: f64) -> GridVolume {
+		let max_temperature = temperature.max_value();
+		self.material.emission_table = (0..EMISSION_TABLE_SIZE)
+			.map(|i| blackbody::emission(max_temperature * i as f64 / (EMISSION_TABLE_SIZE - 1) as f64))
+			.collect();
+		self.material.temperature = Some(temperature);
+		self.material.emission_scale = emission_scale;
+		self
+	}
+
+	fn majorant(&self) -> f64 {
+		self.density.max_value() * self.density_scale
+	}
+}
+
+impl Material for GridVolumeMaterial {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		self.phase_function.scatter(ray, rec)
+	}
+
+	// Absorbed fraction of the collision, (1 - albedo), is what the medium emits
+	fn emitted(&self, rec: &HitRecord) -> Color {
+		let Some(temperature) = &self.temperature else {
+			return Color::new(0., 0., 0.);
+		};
+		let max_temperature = temperature.max_value();
+		if max_temperature <= 0. {
+			return Color::new(0., 0., 0.);
+		}
+
+		let kelvin = temperature.lookup(&self.to_grid.point(&rec.point));
+		let x = f64::clamp(kelvin / max_temperature, 0., 1.) * (EMISSION_TABLE_SIZE - 1) as f64;
+		let i = usize::min(x as usize, EMISSION_TABLE_SIZE - 2);
+		let f = x - i as f64;
+		let emission = (1. - f) * self.emission_table[i] + f * self.emission_table[i + 1];
+
+		self.emission_scale * (Color::new(1., 1., 1.) - self.albedo) * emission
+	}
+}
+
+fn unit_cube() -> Aabb {
+	Aabb::from_points(Point3::new(0., 0., 0.), Point3::new(1., 1., 1.))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::grid_volume::GridVolume;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::transform::Transform;
+	use crate::voxel_grid::VoxelGrid;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn mean_free_path() {
+		// The ray stays in the lower half, where the density is half the majorant so null collisions are
+		// tracked too
+		let grid = VoxelGrid::new(2, 2, 2, vec![1., 1., 2., 2., 1., 1., 2., 2.]);
+		let volume = GridVolume::new(Arc::new(grid), Transform::scale(Vec3::new(100., 100., 100.)), 0.5, Color::new(1., 1., 1.));
+		let ray = Ray::new(Point3::new(-1., 10., 50.), Vec3::new(3., 0., 0.));
+
+		let samples = 20000;
+		let mean = (0..samples)
+			.map(|_| volume.hit(&ray, &Interval::new(0.001, f64::MAX)).unwrap().point.x())
+			.sum::<f64>() / samples as f64;
+
+		assert!(f64::abs(mean - 1. / 0.5) < 0.05, "{}", mean);
+	}
+}
\ No newline at end of file
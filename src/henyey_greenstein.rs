@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+use crate::onb::Onb;
+
+use rand::Rng;
+
+// Anisotropic phase function, `g` > 0 scatters forward and `g` < 0 backward
+pub struct HenyeyGreenstein {
+	albedo: Arc<dyn Texture>,
+	g: f64
+}
+
+impl Material for HenyeyGreenstein {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let direction = HenyeyGreenstein::sample(&ray.direction(), self.g);
+
+		Some((ray.spawn(rec.point, direction), self.albedo.value(rec.u, rec.v, &rec.point)))
+	}
+
+	fn scattering_pdf(&self, ray: &Ray, _rec: &HitRecord, direction: &Vec3) -> f64 {
+		HenyeyGreenstein::phase(ray.direction().unit().dot(direction.unit()), self.g)
+	}
+}
+
+impl HenyeyGreenstein {
+	pub fn new(albedo: Color, g: f64) -> HenyeyGreenstein {
+		HenyeyGreenstein::from_texture(Arc::new(SolidColor::new(albedo)), g)
+	}
+
+	pub fn from_texture(albedo: Arc<dyn Texture>, g: f64) -> HenyeyGreenstein {
+		HenyeyGreenstein {
+			albedo,
+			g: f64::clamp(g, -0.99, 0.99)
+		}
+	}
+
+	// New direction for light travelling along `direction`, the phase function is its own pdf
+	pub fn sample(direction: &Vec3, g: f64) -> Vec3 {
+		let mut rng = rand::thread_rng();
+		let cos_theta = HenyeyGreenstein::sample_cos_theta(g, rng.gen());
+		let sin_theta = f64::sqrt(f64::max(1. - cos_theta * cos_theta, 0.));
+		let phi = 2. * PI * rng.gen::<f64>();
+
+		let onb = Onb::new(direction);
+		onb.local(&Vec3::new(sin_theta * f64::cos(phi), sin_theta * f64::sin(phi), cos_theta))
+	}
+
+	// Density per steradian of scattering `cos_theta` away from the direction of travel
+	pub fn phase(cos_theta: f64, g: f64) -> f64 {
+		let denominator = 1. + g * g - 2. * g * cos_theta;
+
+		(1. - g * g) / (4. * PI * denominator * f64::sqrt(denominator))
+	}
+
+	// Cosine between the incoming and scattered directions, sampled by inverting the phase function's CDF
+	fn sample_cos_theta(g: f64, xi: f64) -> f64 {
+		if f64::abs(g) < 1e-3 {
+			return 1. - 2. * xi;
+		}
+
+		let s = (1. - g * g) / (1. - g + 2. * g * xi);
+
+		f64::clamp((1. + g * g - s * s) / (2. * g), -1., 1.)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use crate::henyey_greenstein::HenyeyGreenstein;
+	use crate::vec3::Vec3;
+
+	#[test]
+	fn mean_cosine() {
+		let direction = Vec3::new(0., 0., 1.);
+		for g in [-0.7, 0., 0.3, 0.9] {
+			let samples = 20000;
+			let mean = (0..samples).map(|_| HenyeyGreenstein::sample(&direction, g).z()).sum::<f64>() / samples as f64;
+
+			assert!(f64::abs(mean - g) < 0.02, "{} != {}", mean, g);
+		}
+	}
+
+	#[test]
+	fn phase_integrates_to_one() {
+		// Midpoint rule over the cosine, the azimuth gives 2 pi
+		let steps = 100000;
+		for g in [-0.5, 0., 0.8] {
+			let integral = (0..steps)
+				.map(|i| -1. + (i as f64 + 0.5) * 2. / steps as f64)
+				.map(|cos_theta| 2. * PI * HenyeyGreenstein::phase(cos_theta, g) * 2. / steps as f64)
+				.sum::<f64>();
+
+			assert!(f64::abs(integral - 1.) < 1e-4, "{}", integral);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+// Fixtures shared by the tests of the quadric and quartic primitives
+use std::sync::Arc;
+
+use crate::hittable::Hittable;
+use crate::interval::Interval;
+use crate::lambertian::Lambertian;
+use crate::color::Color;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::{Vec3, Point3};
+
+pub fn material() -> Arc<dyn Material> {
+	Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+}
+
+// Distance, normal and side of the first hit in front of `origin`
+pub fn hit(object: &dyn Hittable, origin: Point3, direction: Vec3) -> Option<(f64, Vec3, bool)> {
+	let record = object.hit(&Ray::new(origin, direction), &Interval::new(0.001, f64::MAX))?;
+
+	Some((record.t, record.normal, record.front_face))
+}
+
+pub fn assert_hit(hit: Option<(f64, Vec3, bool)>, t: f64, normal: Vec3, front_face: bool) {
+	let (hit_t, hit_normal, hit_front_face) = hit.unwrap();
+	assert!(f64::abs(hit_t - t) < 1e-6, "{} != {}", hit_t, t);
+	assert!((hit_normal - normal.unit()).length() < 1e-6, "{:?} != {:?}", hit_normal, normal);
+	assert_eq!(hit_front_face, front_face);
+}
\ No newline at end of file
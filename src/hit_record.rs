@@ -1,21 +1,35 @@
 use crate::vec3::{Vec3, Point3};
 use crate::ray::Ray;
 use crate::material::Material;
+use crate::onb::Onb;
 
+// `normal` is the shading normal materials work with, `geometric_normal` the true surface orientation,
+// both face the incoming ray
+#[derive(Clone, Copy)]
 pub struct HitRecord<'a> {
 	pub point: Point3,
 	pub normal: Vec3,
+	pub geometric_normal: Vec3,
 	pub t: f64,
+	pub u: f64,
+	pub v: f64,
+	pub dpdu: Vec3,
+	pub dpdv: Vec3,
 	pub front_face: bool,
 	pub material: &'a dyn Material
 }
 
 impl HitRecord<'_> {
-	pub fn new(point: Point3, t: f64, material: &dyn Material) -> HitRecord {
+	pub fn new(point: Point3, t: f64, material: &dyn Material) -> HitRecord<'_> {
 		HitRecord {
 			point,
 			normal: Vec3::new(0., 0., 0.),
+			geometric_normal: Vec3::new(0., 0., 0.),
 			t,
+			u: 0.,
+			v: 0.,
+			dpdu: Vec3::new(0., 0., 0.),
+			dpdv: Vec3::new(0., 0., 0.),
 			front_face: false,
 			material
 		}
@@ -23,6 +37,29 @@ impl HitRecord<'_> {
 
 	pub fn set_face_normal(&mut self, ray: &Ray, outward_normal: &Vec3) {
 		self.front_face = ray.direction().dot(*outward_normal) < 0.;
-		self.normal = if self.front_face { *outward_normal } else { -*outward_normal };
+		self.geometric_normal = if self.front_face { *outward_normal } else { -*outward_normal };
+		self.normal = self.geometric_normal;
+	}
+
+	// Replaces the shading normal, given on the outward side like the geometric one
+	pub fn set_shading_normal(&mut self, outward_normal: &Vec3) {
+		self.normal = if self.front_face { outward_normal.unit() } else { -outward_normal.unit() };
+	}
+
+	// Tangent, bitangent and normal on the outward side of the shading normal, following dp/du and dp/dv
+	// when the shape provides them
+	pub fn tangent_frame(&self) -> Onb {
+		let outward = if self.front_face { self.normal } else { -self.normal };
+		let onb = Onb::with_tangent(&outward, &self.dpdu);
+		if onb.v().dot(self.dpdv) < 0. {
+			return Onb::from_vectors(onb.u(), -onb.v(), onb.w());
+		}
+
+		onb
+	}
+
+	// Directions on different sides of the geometric and shading normals would leak light through the surface
+	pub fn consistent(&self, direction: &Vec3) -> bool {
+		(direction.dot(self.geometric_normal) > 0.) == (direction.dot(self.normal) > 0.)
 	}
 }
\ No newline at end of file
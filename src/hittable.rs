@@ -1,8 +1,81 @@
+use std::sync::Arc;
+
 use crate::ray::Ray;
 use crate::interval::Interval;
 use crate::hit_record::HitRecord;
+use crate::aabb::Aabb;
+use crate::vec3::{Vec3, Point3};
+
+
+pub trait Hittable: Send + Sync {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>>;
+
+	fn bounding_box(&self) -> Aabb;
+
+	// Solid angle density of `random` toward the object, to sample it as an area light in `Camera::lights`
+	fn pdf_value(&self, _origin: &Point3, _direction: &Vec3) -> f64 {
+		0.
+	}
+
+	// Direction from `origin` to a random point on the surface
+	fn random(&self, _origin: &Point3) -> Vec3 {
+		Vec3::new(1., 0., 0.)
+	}
+
+	// Every part of the ray's line inside a closed object, as (entry, exit) pairs sorted along the ray
+	fn spans(&self, ray: &Ray) -> Vec<(HitRecord<'_>, HitRecord<'_>)> {
+		collect_spans(self, ray)
+	}
+}
+
+// Walks every surface crossing inside the bounding box and pairs entries with exits
+fn collect_spans<'a, H: Hittable + ?Sized>(object: &'a H, ray: &Ray) -> Vec<(HitRecord<'a>, HitRecord<'a>)> {
+	let mut spans = Vec::new();
+	let Some(range) = object.bounding_box().clip(ray, &Interval::universe()) else {
+		return spans;
+	};
+
+	let margin = 1e-6 * f64::max(range.size(), 1.);
+	let mut t_min = range.min - margin;
+	let mut entry: Option<HitRecord> = None;
+	for _ in 0..64 {
+		let Some(record) = object.hit(ray, &Interval::new(t_min, range.max + margin)) else {
+			break;
+		};
+		t_min = record.t + margin;
+
+		if record.front_face {
+			if entry.is_none() {
+				entry = Some(record);
+			}
+		}
+		else if let Some(enter) = entry.take() {
+			spans.push((enter, record));
+		}
+	}
+
+	spans
+}
+
+// Lets a shared object be placed in a list directly, e.g. a glass boundary that also bounds a medium
+impl<T: Hittable + ?Sized> Hittable for Arc<T> {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		self.as_ref().hit(ray, t)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.as_ref().bounding_box()
+	}
+
+	fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+		self.as_ref().pdf_value(origin, direction)
+	}
 
+	fn random(&self, origin: &Point3) -> Vec3 {
+		self.as_ref().random(origin)
+	}
 
-pub trait Hittable {
-	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord>;
+	fn spans(&self, ray: &Ray) -> Vec<(HitRecord<'_>, HitRecord<'_>)> {
+		self.as_ref().spans(ray)
+	}
 }
\ No newline at end of file
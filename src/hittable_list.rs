@@ -3,29 +3,43 @@ use std::vec::Vec;
 use crate::hittable::Hittable;
 use crate::hit_record::HitRecord;
 use crate::interval::Interval;
+use crate::aabb::Aabb;
+use crate::vec3::{Vec3, Point3};
+
+use rand::Rng;
 
 pub struct HittableList {
-	objects: Vec<Box<dyn Hittable>>
+	objects: Vec<Box<dyn Hittable>>,
+	bbox: Aabb
 }
 
 impl HittableList {
 	pub fn new() -> HittableList {
 		HittableList { 
-			objects: Vec::new()
+			objects: Vec::new(),
+			bbox: Aabb::empty()
 		}
 	}
 
 	pub fn push(&mut self, object: Box<dyn Hittable>) {
+		self.bbox = Aabb::surrounding(&self.bbox, &object.bounding_box());
 		self.objects.push(object);
 	}
 
 	pub fn clear(&mut self) {
 		self.objects.clear();
+		self.bbox = Aabb::empty();
+	}
+}
+
+impl Default for HittableList {
+	fn default() -> Self {
+		HittableList::new()
 	}
 }
 
 impl Hittable for HittableList {
-	fn hit(&self, ray: &crate::ray::Ray, t: &Interval) -> Option<HitRecord> {
+	fn hit(&self, ray: &crate::ray::Ray, t: &Interval) -> Option<HitRecord<'_>> {
 		let mut record = None;
 		let mut closest_so_far = t.max;
 
@@ -38,4 +52,40 @@ impl Hittable for HittableList {
 
 		record
 	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+
+	fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+		let weight = 1. / self.objects.len() as f64;
+
+		self.objects.iter().map(|object| weight * object.pdf_value(origin, direction)).sum()
+	}
+
+	fn random(&self, origin: &Point3) -> Vec3 {
+		if self.objects.is_empty() {
+			return Vec3::new(1., 0., 0.);
+		}
+
+		let index = rand::thread_rng().gen_range(0..self.objects.len());
+
+		self.objects[index].random(origin)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::hittable::Hittable;
+	use crate::hittable_list::HittableList;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn empty() {
+		let list = HittableList::new();
+		let origin = Point3::new(0., 0., 0.);
+
+		assert_eq!(list.pdf_value(&origin, &Vec3::new(0., 0., 1.)), 0.);
+		assert!(list.random(&origin).length() > 0.);
+	}
 }
\ No newline at end of file
@@ -0,0 +1,185 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::color::Color;
+
+// Rendered frame holding one averaged linear color per pixel, rows go from top to bottom
+pub struct Image {
+	width: usize,
+	height: usize,
+	pixels: Vec<Color>
+}
+
+impl Image {
+	pub fn new(width: usize, height: usize) -> Image {
+		Image {
+			width,
+			height,
+			pixels: vec![Color::new(0., 0., 0.); width * height]
+		}
+	}
+
+	// Plain or binary 8 bit PPM, decoded with the same gamma the writers use
+	pub fn read_ppm(path: &Path) -> io::Result<Image> {
+		let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+		let bytes = fs::read(path)?;
+
+		// Header fields are whitespace separated, comments run to the end of the line
+		let mut position = 0;
+		let mut fields = Vec::new();
+		while fields.len() < 4 {
+			while position < bytes.len() && (bytes[position].is_ascii_whitespace() || bytes[position] == b'#') {
+				if bytes[position] == b'#' {
+					while position < bytes.len() && bytes[position] != b'\n' {
+						position += 1;
+					}
+				}
+				position += 1;
+			}
+
+			let start = position;
+			while position < bytes.len() && !bytes[position].is_ascii_whitespace() {
+				position += 1;
+			}
+			if start == position {
+				return Err(invalid("truncated PPM header"));
+			}
+			fields.push(String::from_utf8_lossy(&bytes[start..position]).into_owned());
+		}
+
+		let number = |field: &str| field.parse::<usize>().map_err(|_| invalid("invalid PPM header"));
+		let (width, height, max) = (number(&fields[1])?, number(&fields[2])?, number(&fields[3])?);
+		if max == 0 || max > 255 {
+			return Err(invalid("only 8 bit PPM images are supported"));
+		}
+
+		let values: Vec<usize> = match fields[0].as_str() {
+			"P6" => bytes.get(position + 1..).unwrap_or(&[]).iter().map(|&byte| usize::from(byte)).collect(),
+			"P3" => String::from_utf8_lossy(&bytes[position..])
+				.split_ascii_whitespace()
+				.map(number)
+				.collect::<io::Result<_>>()?,
+			_ => return Err(invalid("not a PPM image"))
+		};
+		if values.len() < 3 * width * height {
+			return Err(invalid("truncated PPM data"));
+		}
+
+		let decode = |value: usize| f64::powi(value as f64 / max as f64, 2);
+		let pixels = values.chunks(3)
+			.take(width * height)
+			.map(|rgb| Color::new(decode(rgb[0]), decode(rgb[1]), decode(rgb[2])))
+			.collect();
+
+		Ok(Image {
+			width,
+			height,
+			pixels
+		})
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	pub fn get(&self, x: usize, y: usize) -> Color {
+		self.pixels[y * self.width + x]
+	}
+
+	pub fn set(&mut self, x: usize, y: usize, color: Color) {
+		self.pixels[y * self.width + x] = color;
+	}
+
+	// Copies `source` with its top left corner at (x, y)
+	pub fn blit(&mut self, source: &Image, x: usize, y: usize) {
+		for j in 0..source.height {
+			for i in 0..source.width {
+				self.set(x + i, y + j, source.get(i, j));
+			}
+		}
+	}
+
+	// Format picked from the extension, PNG unless it is `.ppm`
+	pub fn save(&self, path: &Path) -> io::Result<()> {
+		let mut file = BufWriter::new(File::create(path)?);
+		if path.extension().is_some_and(|extension| extension == "ppm") {
+			self.write_ppm(&mut file)?;
+		}
+		else {
+			self.write_png(&mut file)?;
+		}
+
+		file.flush()
+	}
+
+	pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+		writeln!(out, "P3")?;
+		writeln!(out, "{} {}", self.width, self.height)?;
+		writeln!(out, "255")?;
+
+		for pixel in &self.pixels {
+			let [r, g, b] = pixel.to_bytes();
+			writeln!(out, "{} {} {}", r, g, b)?;
+		}
+
+		Ok(())
+	}
+
+	// 8 bit RGB PNG
+	pub fn write_png<W: Write>(&self, out: &mut W) -> io::Result<()> {
+		let data: Vec<u8> = self.pixels.iter().flat_map(|pixel| pixel.to_bytes()).collect();
+
+		let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+		encoder.set_color(png::ColorType::Rgb);
+		encoder.set_depth(png::BitDepth::Eight);
+
+		let mut writer = encoder.write_header()?;
+		writer.write_image_data(&data)?;
+		writer.finish()?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::image::Image;
+	use crate::color::Color;
+
+	#[test]
+	fn ppm_round_trip() {
+		let mut image = Image::new(2, 1);
+		image.set(1, 0, Color::new(0.25, 1., 0.));
+
+		let path = std::env::temp_dir().join(format!("tracer_round_trip_{}.ppm", std::process::id()));
+		image.save(&path).unwrap();
+		let read = Image::read_ppm(&path).unwrap();
+		std::fs::remove_file(&path).unwrap();
+
+		assert_eq!((read.width(), read.height()), (2, 1));
+		assert!((read.get(1, 0) - Color::new(0.25, 1., 0.)).length() < 0.02);
+	}
+
+	#[test]
+	fn png_round_trip() {
+		let mut image = Image::new(3, 2);
+		image.set(1, 1, Color::new(1., 0.25, 0.));
+
+		let mut png = Vec::new();
+		image.write_png(&mut png).unwrap();
+
+		let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+		let mut data = vec![0; reader.output_buffer_size()];
+		let info = reader.next_frame(&mut data).unwrap();
+
+		assert_eq!((info.width, info.height), (3, 2));
+		assert_eq!((info.color_type, info.bit_depth), (png::ColorType::Rgb, png::BitDepth::Eight));
+		assert_eq!(&data[12..15], &Color::new(1., 0.25, 0.).to_bytes());
+		assert_eq!(&data[..3], &[0, 0, 0]);
+	}
+}
\ No newline at end of file
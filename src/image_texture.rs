@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::texture::Texture;
+use crate::image::Image;
+use crate::color::Color;
+use crate::vec3::Point3;
+
+// Image looked up by (u, v) with v going up, nearest pixel
+pub struct ImageTexture {
+	image: Arc<Image>
+}
+
+impl Texture for ImageTexture {
+	fn value(&self, u: f64, v: f64, _: &Point3) -> Color {
+		if self.image.width() == 0 || self.image.height() == 0 {
+			return Color::new(0., 1., 1.);
+		}
+
+		let x = (f64::clamp(u, 0., 1.) * self.image.width() as f64) as usize;
+		let y = ((1. - f64::clamp(v, 0., 1.)) * self.image.height() as f64) as usize;
+
+		self.image.get(x.min(self.image.width() - 1), y.min(self.image.height() - 1))
+	}
+}
+
+impl ImageTexture {
+	pub fn new(image: Arc<Image>) -> ImageTexture {
+		ImageTexture {
+			image
+		}
+	}
+}
\ No newline at end of file
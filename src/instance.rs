@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::interval::Interval;
+use crate::transform::Transform;
+use crate::animated_transform::AnimatedTransform;
+use crate::aabb::Aabb;
+use crate::ray::Ray;
+
+// Places a shared object in the world, many instances can point to the same geometry
+pub struct Instance {
+	object: Arc<dyn Hittable>,
+	transform: AnimatedTransform,
+	bbox: Aabb
+}
+
+impl Hittable for Instance {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let transform = self.transform.at(ray.time());
+		let object_ray = transform.inverse().ray(ray);
+
+		let record = self.object.hit(&object_ray, t)?;
+
+		Some(Instance::to_world(&transform, record))
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+
+	fn spans(&self, ray: &Ray) -> Vec<(HitRecord<'_>, HitRecord<'_>)> {
+		let transform = self.transform.at(ray.time());
+		let object_ray = transform.inverse().ray(ray);
+
+		self.object.spans(&object_ray)
+			.into_iter()
+			.map(|(enter, exit)| (Instance::to_world(&transform, enter), Instance::to_world(&transform, exit)))
+			.collect()
+	}
+}
+
+impl Instance {
+	pub fn new(object: Arc<dyn Hittable>, transform: Transform) -> Instance {
+		Instance::moving(object, AnimatedTransform::fixed(transform))
+	}
+
+	// Object following the transform over time, the bounding box covers the whole motion
+	pub fn moving(object: Arc<dyn Hittable>, transform: AnimatedTransform) -> Instance {
+		let bbox = transform.bounding_box(&object.bounding_box());
+
+		Instance {
+			object,
+			transform,
+			bbox
+		}
+	}
+
+	fn to_world<'a>(transform: &Transform, mut record: HitRecord<'a>) -> HitRecord<'a> {
+		record.point = transform.point(&record.point);
+		record.normal = transform.normal(&record.normal).unit();
+		record.geometric_normal = transform.normal(&record.geometric_normal).unit();
+		record.dpdu = transform.vector(&record.dpdu);
+		record.dpdv = transform.vector(&record.dpdv);
+
+		record
+	}
+
+	pub fn transform(&self) -> &AnimatedTransform {
+		&self.transform
+	}
+}
\ No newline at end of file
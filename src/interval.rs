@@ -1,9 +1,17 @@
+#[derive(Clone, Copy, Debug)]
 pub struct Interval {
 	pub min: f64,
 	pub max: f64
 }
 
 impl Interval {
+	pub fn new(min: f64, max: f64) -> Interval {
+		Interval {
+			min,
+			max
+		}
+	}
+
 	pub fn empty() -> Interval {
 		Interval {
 			min: f64::MAX,
@@ -18,6 +26,26 @@ impl Interval {
 		}
 	}
 
+	pub fn union(a: &Interval, b: &Interval) -> Interval {
+		Interval {
+			min: f64::min(a.min, b.min),
+			max: f64::max(a.max, b.max)
+		}
+	}
+
+	pub fn size(&self) -> f64 {
+		self.max - self.min
+	}
+
+	pub fn expand(&self, delta: f64) -> Interval {
+		let padding = delta / 2.;
+
+		Interval {
+			min: self.min - padding,
+			max: self.max + padding
+		}
+	}
+
 	pub fn contains(&self, x: f64) -> bool {
 		self.min <= x && x <= self.max
 	}
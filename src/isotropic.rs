@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+
+// Phase function scattering uniformly in every direction
+pub struct Isotropic {
+	albedo: Arc<dyn Texture>
+}
+
+impl Material for Isotropic {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		Some((ray.spawn(rec.point, Vec3::random_unit()), self.albedo.value(rec.u, rec.v, &rec.point)))
+	}
+
+	fn scattering_pdf(&self, _ray: &Ray, _rec: &HitRecord, _direction: &Vec3) -> f64 {
+		1. / (4. * PI)
+	}
+}
+
+impl Isotropic {
+	pub fn new(albedo: Color) -> Isotropic {
+		Isotropic {
+			albedo: Arc::new(SolidColor::new(albedo))
+		}
+	}
+
+	pub fn from_texture(albedo: Arc<dyn Texture>) -> Isotropic {
+		Isotropic {
+			albedo
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use crate::isotropic::Isotropic;
+	use crate::material::Material;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn uniform_scattering() {
+		let albedo = Color::new(0.2, 0.4, 0.6);
+		let isotropic = Isotropic::new(albedo);
+		let record = HitRecord::new(Point3::new(0., 0., 0.), 1., &isotropic);
+		let ray = Ray::new(Point3::new(-1., 0., 0.), Vec3::new(1., 0., 0.));
+
+		let samples = 20000;
+		let mut mean = Vec3::new(0., 0., 0.);
+		for _ in 0..samples {
+			let (scattered, attenuation) = isotropic.scatter(&ray, &record).unwrap();
+			assert!((attenuation - albedo).length() < 1e-12);
+			mean += scattered.direction().unit() / samples as f64;
+		}
+
+		// No preferred direction, and the density is constant over the sphere
+		assert!(mean.length() < 0.03, "{:?}", mean);
+		assert!(f64::abs(isotropic.scattering_pdf(&ray, &record, &Vec3::new(0., 1., 0.)) - 1. / (4. * PI)) < 1e-12);
+	}
+}
\ No newline at end of file
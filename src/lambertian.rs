@@ -1,28 +1,57 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
 use crate::material::Material;
 use crate::ray::Ray;
 use crate::hit_record::HitRecord;
 use crate::color::Color;
 use crate::vec3::Vec3;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
 
 pub struct Lambertian {
-	albedo: Color
+	albedo: Arc<dyn Texture>,
+	two_sided: bool
 }
 
 impl Material for Lambertian {
-	fn scatter(&self, _: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		if !rec.front_face && !self.two_sided {
+			return None;
+		}
+
 		let mut direction = rec.normal + Vec3::random_unit();
 		if direction.near_zero() {
 			direction = rec.normal;
 		}
 
-		Some((Ray::new(rec.point, direction), self.albedo))
+		Some((ray.spawn(rec.point, direction), self.albedo.value(rec.u, rec.v, &rec.point)))
+	}
+
+	fn scattering_pdf(&self, _ray: &Ray, rec: &HitRecord, direction: &Vec3) -> f64 {
+		if !rec.front_face && !self.two_sided {
+			return 0.;
+		}
+
+		f64::max(rec.normal.dot(direction.unit()), 0.) / PI
 	}
 }
 
 impl Lambertian {
 	pub fn new(albedo: Color) -> Lambertian {
-		Lambertian { 
-			albedo 
+		Lambertian::from_texture(Arc::new(SolidColor::new(albedo)))
+	}
+
+	pub fn from_texture(albedo: Arc<dyn Texture>) -> Lambertian {
+		Lambertian {
+			albedo,
+			two_sided: true
 		}
 	}
+
+	// One sided surfaces absorb everything reaching their back face
+	pub fn two_sided(mut self, two_sided: bool) -> Lambertian {
+		self.two_sided = two_sided;
+		self
+	}
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+// Photographic description of a thin lens. Lengths of the lens and sensor are in millimetres, the scene is
+// in metres unless `units_per_meter` says otherwise
+#[derive(Clone, Copy, Debug)]
+pub struct Lens {
+	pub focal_length: f64,
+	pub f_number: f64,
+	pub sensor_height: f64,
+	pub units_per_meter: f64
+}
+
+impl Lens {
+	// Full frame sensor by default
+	pub fn new(focal_length: f64, f_number: f64) -> Lens {
+		Lens {
+			focal_length,
+			f_number,
+			sensor_height: 24.,
+			units_per_meter: 1.
+		}
+	}
+
+	pub fn sensor_height(mut self, sensor_height: f64) -> Lens {
+		self.sensor_height = sensor_height;
+		self
+	}
+
+	pub fn units_per_meter(mut self, units_per_meter: f64) -> Lens {
+		self.units_per_meter = units_per_meter;
+		self
+	}
+
+	// Vertical field of view in degrees when focused at infinity
+	pub fn fov(&self) -> f64 {
+		2. * f64::atan(self.sensor_height / (2. * self.focal_length)).to_degrees()
+	}
+
+	// Angle of the cone from a point in focus to the edges of the entrance pupil, as `Camera::defocus_angle`
+	pub fn defocus_angle(&self, focus_dist: f64) -> f64 {
+		let aperture_radius = self.focal_length / self.f_number / 2. / 1000. * self.units_per_meter;
+
+		2. * f64::atan(aperture_radius / focus_dist).to_degrees()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lens::Lens;
+
+	#[test]
+	fn normal_lens() {
+		let lens = Lens::new(50., 2.);
+
+		assert!((lens.fov() - 26.9915).abs() < 1e-3);
+		// 25mm pupil seen from 2m
+		assert!((lens.defocus_angle(2.) - 2. * f64::atan(0.0125 / 2.).to_degrees()).abs() < 1e-12);
+	}
+}
\ No newline at end of file
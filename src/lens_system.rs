@@ -0,0 +1,389 @@
+use std::f64;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::vec3::{Vec3, Point3};
+
+use rand::Rng;
+
+type Matrix2 = [[f64; 2]; 2];
+
+const MILLIMETER: f64 = 1e-3;
+const FOCUS_ITERATIONS: usize = 64;
+const PUPIL_GRID: usize = 64;
+const PUPIL_BANDS: usize = 64;
+
+// Spherical surface of the prescription, an aperture stop when the radius is zero. The IOR is the one
+// behind the surface, towards the film
+#[derive(Clone, Copy, Debug)]
+struct Interface {
+	radius: f64,
+	thickness: f64,
+	eta: f64,
+	aperture_radius: f64
+}
+
+// Real lens traced surface by surface from the film (Kolb et al. 1995, pbrt's RealisticCamera). Lens space
+// has the film at z = 0 and the elements along -z, which lines up with the camera frame looking down -z
+#[derive(Clone, Debug)]
+pub struct LensSystem {
+	interfaces: Vec<Interface>,
+	sensor_height: f64,
+	units_per_meter: f64,
+	// Bounds (x min, x max, y min, y max) of the rear element area light gets through from film points
+	// along +x, per band of distance to the film center up to the sensor height
+	exit_pupils: Vec<[f64; 4]>,
+	normalization: f64
+}
+
+impl LensSystem {
+	pub fn load(path: &Path) -> io::Result<LensSystem> {
+		LensSystem::parse(&fs::read_to_string(path)?)
+	}
+
+	// One surface per line from the front element to the rear one: curvature radius, thickness to the next
+	// surface, IOR and aperture diameter, lengths in millimetres. `#` starts a comment, an IOR of 0 is air
+	// and the last thickness, the distance to the film, is set by focusing
+	pub fn parse(text: &str) -> io::Result<LensSystem> {
+		let invalid = |line: usize| io::Error::new(io::ErrorKind::InvalidData, format!("invalid lens prescription at line {}", line + 1));
+
+		let mut interfaces = Vec::new();
+		for (index, line) in text.lines().enumerate() {
+			let line = line.split('#').next().unwrap_or("").trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			let values = line.split_whitespace().map(|value| value.parse::<f64>()).collect::<Result<Vec<_>, _>>().map_err(|_| invalid(index))?;
+			let [radius, thickness, eta, aperture] = values[..] else {
+				return Err(invalid(index));
+			};
+
+			interfaces.push(Interface {
+				radius,
+				thickness,
+				eta: if eta == 0. { 1. } else { eta },
+				aperture_radius: aperture / 2.
+			});
+		}
+		if interfaces.is_empty() {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "empty lens prescription"));
+		}
+
+		let mut system = LensSystem {
+			interfaces,
+			sensor_height: 24.,
+			units_per_meter: 1.,
+			exit_pupils: Vec::new(),
+			normalization: 1.
+		};
+		system.focus(f64::INFINITY);
+
+		Ok(system)
+	}
+
+	// Film height in millimetres, full frame by default
+	pub fn sensor_height(mut self, sensor_height: f64) -> LensSystem {
+		self.sensor_height = sensor_height;
+		self.update_exit_pupils();
+		self
+	}
+
+	pub fn units_per_meter(mut self, units_per_meter: f64) -> LensSystem {
+		self.units_per_meter = units_per_meter;
+		self
+	}
+
+	// Effective focal length in millimetres
+	pub fn focal_length(&self) -> f64 {
+		-1. / self.paraxial()[1][0]
+	}
+
+	// Vertical field of view in degrees, it narrows as the film moves out to focus closer (focus breathing)
+	pub fn fov(&self) -> f64 {
+		let [[a, _], [c, _]] = self.paraxial();
+		let image_distance = self.focal_length() + self.film_distance() + a / c;
+
+		2. * f64::atan(self.sensor_height / (2. * image_distance)).to_degrees()
+	}
+
+	// Moves the film so that objects `distance` scene units in front of it are sharp, using the paraxial
+	// imaging equation of the whole system
+	pub fn focus(&mut self, distance: f64) {
+		let [[a, b], [c, d]] = self.paraxial();
+		let length: f64 = self.interfaces[..self.interfaces.len() - 1].iter().map(|interface| interface.thickness).sum();
+		let distance = distance / (self.units_per_meter * MILLIMETER);
+
+		// The object distance depends on where the film ends up, which converges quickly for real lenses
+		let mut film = -a / c;
+		for _ in 0..FOCUS_ITERATIONS {
+			if !distance.is_finite() {
+				break;
+			}
+
+			let object = distance - length - film;
+			let image = -(a * object + b) / (c * object + d);
+			if object <= 0. || !image.is_finite() || image <= 0. {
+				break;
+			}
+			film = image;
+		}
+
+		if let Some(rear) = self.interfaces.last_mut() {
+			rear.thickness = film;
+		}
+		self.update_exit_pupils();
+	}
+
+	// Ray leaving the front element for the image point (x, y) in [-1, 1] with y up, in the camera frame and
+	// scene units. The weight is the irradiance it brings relative to the image center, which accounts for the
+	// cos^4 falloff and the part of the lens light gets through
+	pub fn ray(&self, x: f64, y: f64, aspect_ratio: f64) -> Option<(Point3, Vec3, f64)> {
+		// Lenses form an inverted image on the film
+		let half_height = self.sensor_height / 2.;
+		let film = Point3::new(-x * half_height * aspect_ratio, -y * half_height, 0.);
+		let rear = self.interfaces.last()?;
+
+		// Pupils are found for film points on +x, the lens being symmetric they are rotated in place
+		let radius = f64::sqrt(film.x() * film.x() + film.y() * film.y());
+		let band = (radius / self.sensor_height * PUPIL_BANDS as f64) as usize;
+		let [x_min, x_max, y_min, y_max] = self.exit_pupils.get(band).copied().unwrap_or_else(|| {
+			[-rear.aperture_radius, rear.aperture_radius, -rear.aperture_radius, rear.aperture_radius]
+		});
+		let area = (x_max - x_min) * (y_max - y_min);
+		if area <= 0. {
+			return None;
+		}
+
+		let mut rng = rand::thread_rng();
+		let (px, py) = (x_min + rng.gen::<f64>() * (x_max - x_min), y_min + rng.gen::<f64>() * (y_max - y_min));
+		let (sin, cos) = if radius > 0. { (film.y() / radius, film.x() / radius) } else { (0., 1.) };
+		let lens = Point3::new(px * cos - py * sin, px * sin + py * cos, -rear.thickness);
+
+		let (origin, direction) = self.trace_from_film(film, lens - film)?;
+		let weight = f64::powi(direction_cosine(&(lens - film)), 4) * area / self.normalization;
+
+		Some((origin * self.units_per_meter * MILLIMETER, direction, weight))
+	}
+
+	// Working f-number at the film center, from the irradiance the exit pupil gives there: an ideal lens of
+	// f-number N gives pi / (4 N^2) times the radiance
+	pub fn f_number(&self) -> f64 {
+		let distance = self.film_distance();
+
+		0.5 * f64::sqrt(f64::consts::PI * distance * distance / self.normalization)
+	}
+
+	fn film_distance(&self) -> f64 {
+		self.interfaces.last().map_or(0., |rear| rear.thickness)
+	}
+
+	// Refracts through every surface from the rear element to the front one, None when the ray is blocked
+	fn trace_from_film(&self, origin: Point3, direction: Vec3) -> Option<(Point3, Vec3)> {
+		let (mut origin, mut direction) = (origin, direction.unit());
+		let mut z = 0.;
+
+		for (i, interface) in self.interfaces.iter().enumerate().rev() {
+			z -= interface.thickness;
+
+			let t = if interface.radius == 0. {
+				(z - origin.z()) / direction.z()
+			}
+			else {
+				LensSystem::intersect(&origin, &direction, interface.radius, z + interface.radius)?
+			};
+			if t < 0. {
+				return None;
+			}
+
+			let hit = origin + t * direction;
+			if hit.x() * hit.x() + hit.y() * hit.y() > interface.aperture_radius * interface.aperture_radius {
+				return None;
+			}
+			origin = hit;
+
+			if interface.radius != 0. {
+				let mut normal = (hit - Point3::new(0., 0., z + interface.radius)).unit();
+				if normal.dot(direction) > 0. {
+					normal = -normal;
+				}
+
+				let eta_t = if i > 0 { self.interfaces[i - 1].eta } else { 1. };
+				direction = refract(&direction, &normal, interface.eta / eta_t)?;
+			}
+		}
+
+		Some((origin, direction))
+	}
+
+	// Surface crossing of a sphere centered on the axis, on the side the ray meets first for that curvature
+	fn intersect(origin: &Point3, direction: &Vec3, radius: f64, center: f64) -> Option<f64> {
+		let o = *origin - Point3::new(0., 0., center);
+		let half_b = o.dot(*direction);
+		let c = o.squared_length() - radius * radius;
+		let discriminant = half_b * half_b - c;
+		if discriminant < 0. {
+			return None;
+		}
+
+		let sqrt_discr = f64::sqrt(discriminant);
+		let (t0, t1) = (-half_b - sqrt_discr, -half_b + sqrt_discr);
+		let closer = (direction.z() > 0.) != (radius < 0.);
+
+		Some(if closer { t0 } else { t1 })
+	}
+
+	// Ray transfer matrix on (height, reduced angle) from just before the front vertex to just after the rear one
+	fn paraxial(&self) -> Matrix2 {
+		let mut matrix = [[1., 0.], [0., 1.]];
+		let mut before = 1.;
+		let last = self.interfaces.len() - 1;
+
+		for (i, interface) in self.interfaces.iter().enumerate() {
+			if interface.radius != 0. {
+				matrix = multiply(&[[1., 0.], [-(interface.eta - before) / interface.radius, 1.]], &matrix);
+			}
+			if i < last {
+				matrix = multiply(&[[1., interface.thickness / interface.eta], [0., 1.]], &matrix);
+			}
+
+			before = interface.eta;
+		}
+
+		matrix
+	}
+
+	// Exit pupil bounds of every band, and the irradiance at the film center the weights are relative to
+	fn update_exit_pupils(&mut self) {
+		self.exit_pupils = (0..PUPIL_BANDS)
+			.map(|band| {
+				let width = self.sensor_height / PUPIL_BANDS as f64;
+				let start = band as f64 * width;
+
+				[start, start + width / 2., start + width]
+					.map(|radius| self.exit_pupil(&Point3::new(radius, 0., 0.)).0)
+					.into_iter()
+					.fold([f64::MAX, f64::MIN, f64::MAX, f64::MIN], |a, b| [a[0].min(b[0]), a[1].max(b[1]), a[2].min(b[2]), a[3].max(b[3])])
+			})
+			.collect();
+
+		let irradiance = self.exit_pupil(&Point3::new(0., 0., 0.)).1;
+		self.normalization = if irradiance > 0. { irradiance } else { 1. };
+	}
+
+	// Bounds of the rear element points `film` sees out through, grown by a grid cell, and the cos^4
+	// weighted area they cover
+	fn exit_pupil(&self, film: &Point3) -> ([f64; 4], f64) {
+		let Some(rear) = self.interfaces.last() else {
+			return ([0.; 4], 0.);
+		};
+
+		let cell = 2. * rear.aperture_radius / PUPIL_GRID as f64;
+		let mut bounds = [f64::MAX, f64::MIN, f64::MAX, f64::MIN];
+		let mut irradiance = 0.;
+		for i in 0..PUPIL_GRID {
+			for j in 0..PUPIL_GRID {
+				let x = -rear.aperture_radius + (i as f64 + 0.5) * cell;
+				let y = -rear.aperture_radius + (j as f64 + 0.5) * cell;
+				let lens = Point3::new(x, y, -rear.thickness);
+				if self.trace_from_film(*film, lens - *film).is_none() {
+					continue;
+				}
+
+				bounds = [bounds[0].min(x - cell), bounds[1].max(x + cell), bounds[2].min(y - cell), bounds[3].max(y + cell)];
+				irradiance += f64::powi(direction_cosine(&(lens - *film)), 4) * cell * cell;
+			}
+		}
+
+		if irradiance == 0. {
+			return ([0.; 4], 0.);
+		}
+
+		(bounds, irradiance)
+	}
+}
+
+fn direction_cosine(direction: &Vec3) -> f64 {
+	direction.z().abs() / direction.length()
+}
+
+// Snell's law with `normal` facing the incoming unit direction, None on total internal reflection
+fn refract(direction: &Vec3, normal: &Vec3, eta: f64) -> Option<Vec3> {
+	let cos_i = -direction.dot(*normal);
+	let sin2_t = eta * eta * f64::max(1. - cos_i * cos_i, 0.);
+	if sin2_t >= 1. {
+		return None;
+	}
+
+	let cos_t = f64::sqrt(1. - sin2_t);
+	Some(eta * *direction + (eta * cos_i - cos_t) * *normal)
+}
+
+fn multiply(lhs: &Matrix2, rhs: &Matrix2) -> Matrix2 {
+	[
+		[lhs[0][0] * rhs[0][0] + lhs[0][1] * rhs[1][0], lhs[0][0] * rhs[0][1] + lhs[0][1] * rhs[1][1]],
+		[lhs[1][0] * rhs[0][0] + lhs[1][1] * rhs[1][0], lhs[1][0] * rhs[0][1] + lhs[1][1] * rhs[1][1]]
+	]
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lens_system::LensSystem;
+	use crate::vec3::Point3;
+
+	// Double Gauss from US patent 2,673,491 scaled to 50mm, as shipped with pbrt
+	const DOUBLE_GAUSS: &str = "
+		# radius thickness ior aperture
+		29.475   3.76   1.67   25.2
+		84.83    0.12   1      25.2
+		19.275   4.025  1.67   23
+		40.77    3.275  1.699  23
+		12.75    5.705  1      18
+		0        4.5    0      17.1
+		-14.495  1.18   1.603  17
+		40.77    6.065  1.658  20
+		-20.385  0.19   1      20
+		437.065  3.22   1.717  20
+		-39.73   0      1      20
+	";
+
+	#[test]
+	fn focal_length() {
+		let system = LensSystem::parse(DOUBLE_GAUSS).unwrap();
+
+		assert!((system.focal_length() - 50.).abs() < 2., "{}", system.focal_length());
+	}
+
+	#[test]
+	fn focused_at_infinity() {
+		// A paraxial ray from the film center leaves the lens parallel to the axis
+		let system = LensSystem::parse(DOUBLE_GAUSS).unwrap();
+		let (_, direction) = system.trace_from_film(Point3::new(0., 0., 0.), Point3::new(0.1, 0., -system.film_distance())).unwrap();
+
+		assert!(direction.x().abs() < 1e-3 * direction.z().abs(), "{:?}", direction);
+	}
+
+	#[test]
+	fn focus_moves_film_out() {
+		let mut system = LensSystem::parse(DOUBLE_GAUSS).unwrap();
+		let infinity = system.film_distance();
+		system.focus(1.);
+
+		assert!(system.film_distance() > infinity);
+	}
+
+	#[test]
+	fn f_number() {
+		let system = LensSystem::parse(DOUBLE_GAUSS).unwrap();
+
+		// The patent lens is an f/2
+		assert!((system.f_number() - 2.).abs() < 0.1, "{}", system.f_number());
+	}
+
+	#[test]
+	fn rejects_malformed_lines() {
+		assert!(LensSystem::parse("29.475 3.76 1.67").is_err());
+		assert!(LensSystem::parse("# nothing").is_err());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+pub mod vec3;
+pub mod color;
+pub mod ray;
+pub mod hittable;
+pub mod sphere;
+pub mod hittable_list;
+pub mod interval;
+pub mod camera;
+pub mod camera_animation;
+pub mod image;
+pub mod projection;
+pub mod stereo;
+pub mod aperture;
+pub mod lens;
+pub mod lens_system;
+pub mod exposure;
+pub mod hit_record;
+pub mod material;
+pub mod lambertian;
+pub mod metal;
+pub mod dielectric;
+pub mod aabb;
+pub mod transform;
+pub mod animated_transform;
+pub mod track;
+pub mod instance;
+pub mod texture;
+pub mod solid_color;
+pub mod checker_texture;
+pub mod image_texture;
+pub mod material_table;
+pub mod quad;
+pub mod plane;
+pub mod disk;
+pub mod cuboid;
+pub mod diffuse_light;
+pub mod polynomial;
+pub mod cylinder;
+pub mod cone;
+pub mod torus;
+pub mod capsule;
+pub mod csg;
+pub mod sdf;
+pub mod sdf_shape;
+pub mod onb;
+pub mod isotropic;
+pub mod henyey_greenstein;
+pub mod constant_medium;
+pub mod cie;
+pub mod blackbody;
+pub mod voxel_grid;
+pub mod grid_volume;
+pub mod fresnel;
+pub mod microfacet;
+pub mod conductor;
+pub mod medium_stack;
+pub mod spectrum;
+pub mod dispersion;
+pub mod principled;
+pub mod mix;
+pub mod coated;
+pub mod oren_nayar;
+pub mod diffuse_transmission;
+pub mod thin_film;
+pub mod subsurface;
+pub mod normal_map;
+pub mod bump_map;
+pub mod alpha_mask;
+
+#[cfg(test)]
+mod hit_assertions;
\ No newline at end of file
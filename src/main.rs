@@ -1,31 +1,20 @@
-pub mod vec3;
-pub mod color;
-pub mod ray;
-pub mod hittable;
-pub mod sphere;
-pub mod hittable_list;
-pub mod interval;
-pub mod camera;
-pub mod hit_record;
-pub mod material;
-pub mod lambertian;
-pub mod metal;
-pub mod dielectric;
+use std::sync::Arc;
 
-use crate::vec3::{Vec3, Point3};
-use crate::hittable_list::HittableList;
-use crate::sphere::Sphere;
-use crate::camera::Camera;
-use crate::lambertian::Lambertian;
-use crate::metal::Metal;
-use crate::dielectric::Dielectric;
-use crate::color::Color;
+use tracer::vec3::{Vec3, Point3};
+use tracer::hittable_list::HittableList;
+use tracer::sphere::Sphere;
+use tracer::camera::Camera;
+use tracer::lambertian::Lambertian;
+use tracer::metal::Metal;
+use tracer::dielectric::Dielectric;
+use tracer::color::Color;
+use tracer::material::Material;
 
 use rand::Rng;
 
 fn main() {
-    let mat_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
-   
+    let mat_ground: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let mat_glass: Arc<dyn Material> = Arc::new(Dielectric::new(1.5));
 
     let mut world = HittableList::new();
     world.push(Box::new(Sphere::new(Vec3::new(0., -1000., -1.), 1000., mat_ground)));
@@ -40,23 +29,23 @@ fn main() {
 
                 if choose_mat < 0.8 {
                     let albedo = Color::random() * Color::random();
-                    world.push(Box::new(Sphere::new(center, 0.2, Box::new(Lambertian::new(albedo)))));
+                    world.push(Box::new(Sphere::new(center, 0.2, Arc::new(Lambertian::new(albedo)))));
                 }
                 else if choose_mat < 0.95 {
                     let albedo = Color::borned_random(0.5, 1.);
-                    let fuzz = rng.gen_range((0.)..(0.5));
-                    world.push(Box::new(Sphere::new(center, 0.2, Box::new(Metal::new(albedo, fuzz)))));
+                    let fuzz = rng.gen_range(0. ..0.5);
+                    world.push(Box::new(Sphere::new(center, 0.2, Arc::new(Metal::new(albedo, fuzz)))));
                 }
                 else {
-                    world.push(Box::new(Sphere::new(center, 0.2, Box::new(Dielectric::new(1.5)))));
+                    world.push(Box::new(Sphere::new(center, 0.2, mat_glass.clone())));
                 }
             }
         }
     }
 
-    world.push(Box::new(Sphere::new(Point3::new(0., 1., 0.), 1., Box::new(Dielectric::new(1.5)))));
-    world.push(Box::new(Sphere::new(Point3::new(-4., 1., 0.), 1., Box::new(Lambertian::new(Color::new(0.4, 0.2, 0.1))))));
-    world.push(Box::new(Sphere::new(Point3::new(4., 1., 0.), 1., Box::new(Metal::new(Color::new(0.7, 0.6, 0.5), 0.)))));
+    world.push(Box::new(Sphere::new(Point3::new(0., 1., 0.), 1., mat_glass)));
+    world.push(Box::new(Sphere::new(Point3::new(-4., 1., 0.), 1., Arc::new(Lambertian::new(Color::new(0.4, 0.2, 0.1))))));
+    world.push(Box::new(Sphere::new(Point3::new(4., 1., 0.), 1., Arc::new(Metal::new(Color::new(0.7, 0.6, 0.5), 0.)))));
 
     let mut camera = Camera::new();
 
@@ -1,7 +1,18 @@
 use crate::ray::Ray;
 use crate::hit_record::HitRecord;
 use crate::color::Color;
+use crate::vec3::Vec3;
 
-pub trait Material {
+pub trait Material: Send + Sync {
 	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)>;
+
+	fn emitted(&self, _rec: &HitRecord) -> Color {
+		Color::new(0., 0., 0.)
+	}
+
+	// Density `scatter` picks `direction` with, for materials whose attenuation doesn't depend on the
+	// direction. The camera mixes lights into their sampling, materials leaving it at 0 are never light sampled
+	fn scattering_pdf(&self, _ray: &Ray, _rec: &HitRecord, _direction: &Vec3) -> f64 {
+		0.
+	}
 }
\ No newline at end of file
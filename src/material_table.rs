@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::texture::Texture;
+use crate::principled::Principled;
+use crate::solid_color::SolidColor;
+use crate::color::Color;
+
+// Named materials and textures, defined once and shared by every object that uses them
+pub struct MaterialTable {
+	materials: HashMap<String, Arc<dyn Material>>,
+	textures: HashMap<String, Arc<dyn Texture>>
+}
+
+impl MaterialTable {
+	pub fn new() -> MaterialTable {
+		MaterialTable {
+			materials: HashMap::new(),
+			textures: HashMap::new()
+		}
+	}
+
+	pub fn add_material(&mut self, name: &str, material: Arc<dyn Material>) -> Arc<dyn Material> {
+		self.materials.insert(name.to_string(), material.clone());
+
+		material
+	}
+
+	pub fn material(&self, name: &str) -> Option<Arc<dyn Material>> {
+		self.materials.get(name).cloned()
+	}
+
+	pub fn add_texture(&mut self, name: &str, texture: Arc<dyn Texture>) -> Arc<dyn Texture> {
+		self.textures.insert(name.to_string(), texture.clone());
+
+		texture
+	}
+
+	pub fn texture(&self, name: &str) -> Option<Arc<dyn Texture>> {
+		self.textures.get(name).cloned()
+	}
+
+	pub fn load_materials<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+		self.parse_materials(&fs::read_to_string(path)?)
+	}
+
+	// Principled materials, each starting with a `material <name>` line followed by `<parameter> <value>`
+	// lines. Values are a number, three numbers for a colour, or the name of a texture already in the table.
+	// Lines starting with # are comments
+	pub fn parse_materials(&mut self, text: &str) -> io::Result<()> {
+		let invalid = |line: usize| io::Error::new(io::ErrorKind::InvalidData, format!("invalid material definition at line {}", line + 1));
+
+		let mut current: Option<(String, Principled)> = None;
+		for (index, line) in text.lines().enumerate() {
+			let line = line.split('#').next().unwrap_or("").trim();
+			let mut tokens = line.split_whitespace();
+			let Some(keyword) = tokens.next() else {
+				continue;
+			};
+			let arguments = tokens.collect::<Vec<_>>();
+
+			if keyword == "material" {
+				let [name] = arguments[..] else {
+					return Err(invalid(index));
+				};
+				if let Some((name, material)) = current.take() {
+					self.add_material(&name, Arc::new(material));
+				}
+				current = Some((name.to_string(), Principled::new(Color::new(0.8, 0.8, 0.8))));
+				continue;
+			}
+
+			let (name, material) = current.take().ok_or_else(|| invalid(index))?;
+			let value = self.parameter_value(&arguments).ok_or_else(|| invalid(index))?;
+			current = Some((name, material.parameter(keyword, value).ok_or_else(|| invalid(index))?));
+		}
+		if let Some((name, material)) = current {
+			self.add_material(&name, Arc::new(material));
+		}
+
+		Ok(())
+	}
+
+	fn parameter_value(&self, arguments: &[&str]) -> Option<Arc<dyn Texture>> {
+		let numbers = arguments.iter().map(|argument| argument.parse::<f64>()).collect::<Result<Vec<_>, _>>();
+
+		match (numbers.as_deref(), arguments) {
+			(Ok(&[value]), _) => Some(Arc::new(SolidColor::new(Color::new(value, value, value)))),
+			(Ok(&[r, g, b]), _) => Some(Arc::new(SolidColor::new(Color::new(r, g, b)))),
+			(Err(_), [name]) => self.texture(name),
+			_ => None
+		}
+	}
+}
+
+impl Default for MaterialTable {
+	fn default() -> Self {
+		MaterialTable::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::material_table::MaterialTable;
+	use crate::solid_color::SolidColor;
+	use crate::color::Color;
+
+	#[test]
+	fn parse_materials() {
+		let mut table = MaterialTable::new();
+		table.add_texture("scratches", Arc::new(SolidColor::new(Color::new(0.2, 0.2, 0.2))));
+
+		let definitions = "
+			# Red car paint
+			material car_paint
+			base_color 0.8 0.1 0.1
+			metallic 0.3
+			roughness scratches
+			clearcoat 1
+
+			material glass
+			transmission 1
+			ior 1.45
+		";
+		table.parse_materials(definitions).unwrap();
+
+		assert!(table.material("car_paint").is_some());
+		assert!(table.material("glass").is_some());
+
+		assert!(table.parse_materials("roughness 0.5").is_err());
+		assert!(table.parse_materials("material a\nshininess 0.5").is_err());
+		assert!(table.parse_materials("material a\nroughness missing_texture").is_err());
+		assert!(table.parse_materials("material a\nbase_color 0.5 0.5").is_err());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,146 @@
+use crate::color::Color;
+use crate::dispersion::Dispersion;
+
+const CAPACITY: usize = 4;
+
+// Dielectric volume a ray is travelling through
+#[derive(Clone, Copy, Debug)]
+pub struct Medium {
+	pub id: usize,
+	pub priority: u32,
+	pub ior: f64,
+	pub dispersion: Option<Dispersion>,
+	pub absorption: Color,
+	pub scattering: Color,
+	pub anisotropy: f64
+}
+
+impl Medium {
+	pub fn ior_at(&self, lambda: Option<f64>) -> f64 {
+		match (self.dispersion, lambda) {
+			(Some(dispersion), Some(lambda)) => dispersion.ior(lambda),
+			_ => self.ior
+		}
+	}
+}
+
+// Media entered along a path, used to resolve nested dielectrics with priorities
+// (Schmidt and Budge 2002): the highest priority medium wins where volumes overlap
+#[derive(Clone, Copy, Debug)]
+pub struct MediumStack {
+	entries: [Option<Medium>; CAPACITY]
+}
+
+impl MediumStack {
+	pub fn new() -> MediumStack {
+		MediumStack {
+			entries: [None; CAPACITY]
+		}
+	}
+
+	pub fn contains(&self, id: usize) -> bool {
+		self.entries.iter().flatten().any(|medium| medium.id == id)
+	}
+
+	// Medium filling the current location, the most recently entered wins ties
+	pub fn current(&self) -> Option<Medium> {
+		self.current_without(usize::MAX)
+	}
+
+	pub fn current_without(&self, id: usize) -> Option<Medium> {
+		self.entries.iter()
+			.flatten()
+			.filter(|medium| medium.id != id)
+			.fold(None, |best: Option<Medium>, medium| match best {
+				Some(best) if best.priority > medium.priority => Some(best),
+				_ => Some(*medium)
+			})
+	}
+
+	// Whether a medium other than `id` has a strictly higher priority, making the interface a false one
+	pub fn overrides(&self, id: usize, priority: u32) -> bool {
+		self.entries.iter().flatten().any(|medium| medium.id != id && medium.priority > priority)
+	}
+
+	// Deepest nesting is bounded. Past `CAPACITY` volumes the extra ones are ignored, so their insides render
+	// as the medium around them, which debug builds flag
+	pub fn push(&mut self, medium: Medium) {
+		let slot = self.entries.iter_mut().find(|slot| slot.is_none());
+		debug_assert!(slot.is_some(), "more than {} nested dielectric volumes", CAPACITY);
+
+		if let Some(slot) = slot {
+			*slot = Some(medium);
+		}
+	}
+
+	pub fn remove(&mut self, id: usize) {
+		if let Some(index) = self.entries.iter().rposition(|slot| slot.is_some_and(|medium| medium.id == id)) {
+			self.entries[index..].rotate_left(1);
+			self.entries[CAPACITY - 1] = None;
+		}
+	}
+}
+
+impl Default for MediumStack {
+	fn default() -> Self {
+		MediumStack::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::color::Color;
+	use crate::medium_stack::{Medium, MediumStack, CAPACITY};
+
+	fn medium(id: usize, priority: u32) -> Medium {
+		Medium {
+			id,
+			priority,
+			ior: 1. + id as f64 / 10.,
+			dispersion: None,
+			absorption: Color::new(0., 0., 0.),
+			scattering: Color::new(0., 0., 0.),
+			anisotropy: 0.
+		}
+	}
+
+	#[test]
+	fn push_and_remove() {
+		let mut media = MediumStack::new();
+		assert!(media.current().is_none());
+
+		media.push(medium(1, 0));
+		media.push(medium(2, 0));
+		// Equal priorities go to the latest entered
+		assert_eq!(media.current().unwrap().id, 2);
+		assert_eq!(media.current_without(2).unwrap().id, 1);
+
+		// Leaving in another order than entering, as with overlapping volumes
+		media.remove(1);
+		assert!(!media.contains(1));
+		assert_eq!(media.current().unwrap().id, 2);
+		media.remove(2);
+		assert!(media.current().is_none());
+	}
+
+	#[test]
+	fn priorities() {
+		let mut media = MediumStack::new();
+		media.push(medium(1, 2));
+		media.push(medium(2, 1));
+
+		assert_eq!(media.current().unwrap().id, 1);
+		assert!(media.overrides(2, 1));
+		assert!(!media.overrides(1, 2));
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic]
+	fn overflow() {
+		let mut media = MediumStack::new();
+		for id in 0..=CAPACITY {
+			media.push(medium(id, 0));
+		}
+	}
+}
\ No newline at end of file
@@ -17,7 +17,7 @@ impl Material for Metal {
 			return None;
 		}
 
-		Some((Ray::new(rec.point, scattered), self.albedo))
+		Some((ray.spawn(rec.point, scattered), self.albedo))
 	}
 }
 
@@ -0,0 +1,98 @@
+use std::f64::consts::PI;
+
+use crate::vec3::Vec3;
+
+// Trowbridge-Reitz (GGX) distribution, directions are in the local frame where z is the normal
+#[derive(Clone, Copy, Debug)]
+pub struct Ggx {
+	alpha_x: f64,
+	alpha_y: f64
+}
+
+impl Ggx {
+	pub fn new(alpha_x: f64, alpha_y: f64) -> Ggx {
+		Ggx {
+			alpha_x: f64::max(alpha_x, 1e-4),
+			alpha_y: f64::max(alpha_y, 1e-4)
+		}
+	}
+
+	// Perceptual roughness in [0, 1], alpha is its square
+	pub fn from_roughness(roughness_x: f64, roughness_y: f64) -> Ggx {
+		Ggx::new(roughness_x * roughness_x, roughness_y * roughness_y)
+	}
+
+	pub fn is_smooth(&self) -> bool {
+		f64::max(self.alpha_x, self.alpha_y) < 1e-3
+	}
+
+	pub fn lambda(&self, w: &Vec3) -> f64 {
+		let cos2 = w.z() * w.z();
+		if cos2 == 0. {
+			return f64::INFINITY;
+		}
+
+		let tan2 = (self.alpha_x * self.alpha_x * w.x() * w.x() + self.alpha_y * self.alpha_y * w.y() * w.y()) / cos2;
+
+		(f64::sqrt(1. + tan2) - 1.) / 2.
+	}
+
+	pub fn g1(&self, w: &Vec3) -> f64 {
+		1. / (1. + self.lambda(w))
+	}
+
+	// Height correlated Smith shadowing-masking
+	pub fn g2(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+		1. / (1. + self.lambda(wo) + self.lambda(wi))
+	}
+
+	// Samples a microfacet normal visible from `wo` (Heitz 2018), `wo` must be above the surface
+	pub fn sample_visible_normal(&self, wo: &Vec3, u1: f64, u2: f64) -> Vec3 {
+		let vh = Vec3::new(self.alpha_x * wo.x(), self.alpha_y * wo.y(), wo.z()).unit();
+
+		let lensq = vh.x() * vh.x() + vh.y() * vh.y();
+		let t1 = if lensq > 0. { Vec3::new(-vh.y(), vh.x(), 0.) / f64::sqrt(lensq) } else { Vec3::new(1., 0., 0.) };
+		let t2 = vh.cross(t1);
+
+		let r = f64::sqrt(u1);
+		let phi = 2. * PI * u2;
+		let p1 = r * f64::cos(phi);
+		let s = 0.5 * (1. + vh.z());
+		let p2 = (1. - s) * f64::sqrt(1. - p1 * p1) + s * r * f64::sin(phi);
+
+		let nh = p1 * t1 + p2 * t2 + f64::sqrt(f64::max(1. - p1 * p1 - p2 * p2, 0.)) * vh;
+
+		Vec3::new(self.alpha_x * nh.x(), self.alpha_y * nh.y(), f64::max(nh.z(), 1e-6)).unit()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::microfacet::Ggx;
+	use crate::vec3::Vec3;
+
+	#[test]
+	fn smith_masking() {
+		let ggx = Ggx::new(0.5, 0.5);
+		let grazing = Vec3::new(1., 0., 1e-3).unit();
+		let oblique = Vec3::new(1., 0., 1.).unit();
+
+		assert!(f64::abs(ggx.g1(&Vec3::new(0., 0., 1.)) - 1.) < 1e-12);
+		assert!(ggx.g1(&grazing) < 0.01);
+		assert!(ggx.g2(&oblique, &oblique) < ggx.g1(&oblique));
+		// Only the roughness along the direction matters
+		assert!(f64::abs(Ggx::new(0.5, 0.01).g1(&oblique) - ggx.g1(&oblique)) < 1e-12);
+	}
+
+	#[test]
+	fn visible_normals_face_the_viewer() {
+		let ggx = Ggx::new(0.3, 0.6);
+		let wo = Vec3::new(0.6, 0.3, 0.5).unit();
+		for i in 0..100 {
+			let m = ggx.sample_visible_normal(&wo, (i as f64 + 0.5) / 100., f64::fract(i as f64 * 0.618));
+
+			assert!(f64::abs(m.length() - 1.) < 1e-9);
+			assert!(m.z() > 0. && wo.dot(m) >= 0.);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+
+use rand::Rng;
+
+// Blend of two materials, `second` is picked with the probability read from the first channel of the weight
+pub struct Mix {
+	first: Arc<dyn Material>,
+	second: Arc<dyn Material>,
+	weight: Arc<dyn Texture>
+}
+
+impl Material for Mix {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		if rand::thread_rng().gen::<f64>() < self.weight(rec) {
+			return self.second.scatter(ray, rec);
+		}
+
+		self.first.scatter(ray, rec)
+	}
+
+	fn emitted(&self, rec: &HitRecord) -> Color {
+		let weight = self.weight(rec);
+
+		(1. - weight) * self.first.emitted(rec) + weight * self.second.emitted(rec)
+	}
+}
+
+impl Mix {
+	pub fn new(first: Arc<dyn Material>, second: Arc<dyn Material>, weight: f64) -> Mix {
+		Mix::from_texture(first, second, Arc::new(SolidColor::new(Color::new(weight, weight, weight))))
+	}
+
+	pub fn from_texture(first: Arc<dyn Material>, second: Arc<dyn Material>, weight: Arc<dyn Texture>) -> Mix {
+		Mix {
+			first,
+			second,
+			weight
+		}
+	}
+
+	fn weight(&self, rec: &HitRecord) -> f64 {
+		f64::clamp(self.weight.value(rec.u, rec.v, &rec.point).x(), 0., 1.)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::mix::Mix;
+	use crate::material::Material;
+	use crate::lambertian::Lambertian;
+	use crate::diffuse_light::DiffuseLight;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn weights_select_a_component() {
+		let (red, blue) = (Color::new(1., 0., 0.), Color::new(0., 0., 1.));
+		let ray = Ray::new(Point3::new(0., 0., 1.), Vec3::new(0., 0., -1.));
+
+		for (weight, expected) in [(0., red), (1., blue)] {
+			let mix = Mix::new(Arc::new(Lambertian::new(red)), Arc::new(Lambertian::new(blue)), weight);
+			let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., &mix);
+			record.normal = Vec3::new(0., 0., 1.);
+			record.front_face = true;
+			for _ in 0..100 {
+				assert!((mix.scatter(&ray, &record).unwrap().1 - expected).length() < 1e-12);
+			}
+
+			let lights = Mix::new(Arc::new(DiffuseLight::new(red)), Arc::new(DiffuseLight::new(blue)), weight);
+			let record = HitRecord::new(Point3::new(0., 0., 0.), 1., &lights);
+			assert!((lights.emitted(&record) - expected).length() < 1e-12);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+
+// Tangent space normal map over any material, colours in [0, 1] encode the components in [-1, 1]
+// along the tangent, bitangent and normal of the surface
+pub struct NormalMap {
+	material: Arc<dyn Material>,
+	map: Arc<dyn Texture>,
+	strength: f64
+}
+
+impl Material for NormalMap {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let shading = self.shading(ray, rec);
+		let (scattered, attenuation) = self.material.scatter(ray, &shading)?;
+		if !shading.consistent(&scattered.direction()) {
+			return None;
+		}
+
+		Some((scattered, attenuation))
+	}
+
+	fn emitted(&self, rec: &HitRecord) -> Color {
+		self.material.emitted(rec)
+	}
+}
+
+impl NormalMap {
+	pub fn new(material: Arc<dyn Material>, map: Arc<dyn Texture>) -> NormalMap {
+		NormalMap {
+			material,
+			map,
+			strength: 1.
+		}
+	}
+
+	// Scales the tangential components, 0 leaves the surface flat
+	pub fn strength(mut self, strength: f64) -> NormalMap {
+		self.strength = strength;
+		self
+	}
+
+	// Hits seen from behind their mapped normal keep the original one
+	fn shading<'a>(&self, ray: &Ray, rec: &HitRecord<'a>) -> HitRecord<'a> {
+		let encoded = self.map.value(rec.u, rec.v, &rec.point);
+		let local = Vec3::new(
+			self.strength * (2. * encoded.x() - 1.),
+			self.strength * (2. * encoded.y() - 1.),
+			2. * encoded.z() - 1.
+		);
+		if local.near_zero() {
+			return *rec;
+		}
+
+		let mut shading = *rec;
+		shading.set_shading_normal(&rec.tangent_frame().local(&local));
+		if ray.direction().dot(shading.normal) >= 0. {
+			return *rec;
+		}
+
+		shading
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::normal_map::NormalMap;
+	use crate::lambertian::Lambertian;
+	use crate::solid_color::SolidColor;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	// Shading normal for a map of a single colour, on a surface whose tangent runs along y
+	fn shade(encoded: Color, dpdv: Vec3) -> Vec3 {
+		let map = NormalMap::new(Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))), Arc::new(SolidColor::new(encoded)));
+		let ray = Ray::new(Point3::new(0., 0., 1.), Vec3::new(0., 0., -1.));
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., &map);
+		record.set_face_normal(&ray, &Vec3::new(0., 0., 1.));
+		record.dpdu = Vec3::new(0., 2., 0.);
+		record.dpdv = dpdv;
+
+		map.shading(&ray, &record).normal
+	}
+
+	#[test]
+	fn flat_map() {
+		assert!((shade(Color::new(0.5, 0.5, 1.), Vec3::new(-1., 0., 0.)) - Vec3::new(0., 0., 1.)).length() < 1e-12);
+	}
+
+	#[test]
+	fn tilts_within_the_tangent_frame() {
+		let tilted = shade(Color::new(1., 0.5, 1.), Vec3::new(-1., 0., 0.));
+		assert!((tilted - Vec3::new(0., 1., 1.).unit()).length() < 1e-12, "{:?}", tilted);
+
+		// Bitangents follow dp/dv, also when it makes the frame left handed
+		let tilted = shade(Color::new(0.5, 1., 1.), Vec3::new(-1., 0., 0.));
+		assert!((tilted - Vec3::new(-1., 0., 1.).unit()).length() < 1e-12, "{:?}", tilted);
+		let tilted = shade(Color::new(0.5, 1., 1.), Vec3::new(1., 0., 0.));
+		assert!((tilted - Vec3::new(1., 0., 1.).unit()).length() < 1e-12, "{:?}", tilted);
+	}
+}
\ No newline at end of file
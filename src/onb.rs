@@ -0,0 +1,68 @@
+use crate::vec3::Vec3;
+
+// Orthonormal basis built around `w`, used to sample directions relative to a normal
+pub struct Onb {
+	u: Vec3,
+	v: Vec3,
+	w: Vec3
+}
+
+impl Onb {
+	pub fn new(w: &Vec3) -> Onb {
+		let w = w.unit();
+		let helper = if f64::abs(w.x()) > 0.9 { Vec3::new(0., 1., 0.) } else { Vec3::new(1., 0., 0.) };
+		let v = w.cross(helper).unit();
+		let u = v.cross(w);
+
+		Onb {
+			u,
+			v,
+			w
+		}
+	}
+
+	pub fn from_vectors(u: Vec3, v: Vec3, w: Vec3) -> Onb {
+		Onb {
+			u,
+			v,
+			w
+		}
+	}
+
+	// Basis whose `u` follows `tangent` projected on the plane orthogonal to `w`
+	pub fn with_tangent(w: &Vec3, tangent: &Vec3) -> Onb {
+		let w = w.unit();
+		let projected = *tangent - w.dot(*tangent) * w;
+		if projected.squared_length() < 1e-16 {
+			return Onb::new(&w);
+		}
+
+		let u = projected.unit();
+
+		Onb {
+			u,
+			v: w.cross(u),
+			w
+		}
+	}
+
+	pub fn u(&self) -> Vec3 {
+		self.u
+	}
+
+	pub fn v(&self) -> Vec3 {
+		self.v
+	}
+
+	pub fn w(&self) -> Vec3 {
+		self.w
+	}
+
+	pub fn local(&self, a: &Vec3) -> Vec3 {
+		a.x() * self.u + a.y() * self.v + a.z() * self.w
+	}
+
+	pub fn to_local(&self, a: &Vec3) -> Vec3 {
+		Vec3::new(a.dot(self.u), a.dot(self.v), a.dot(self.w))
+	}
+}
\ No newline at end of file
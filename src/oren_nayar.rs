@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+use crate::onb::Onb;
+
+// Rough diffuse surface made of V-shaped Lambertian facets (Oren and Nayar 1994),
+// `sigma` is the standard deviation of the facet angle in degrees
+pub struct OrenNayar {
+	albedo: Arc<dyn Texture>,
+	a: f64,
+	b: f64,
+	two_sided: bool
+}
+
+impl Material for OrenNayar {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		if !rec.front_face && !self.two_sided {
+			return None;
+		}
+
+		let onb = Onb::new(&rec.normal);
+		let wo = onb.to_local(&-ray.direction().unit());
+		let wi = Vec3::random_cosine_direction();
+
+		let sin_theta_o = f64::sqrt(f64::max(1. - wo.z() * wo.z(), 0.));
+		let sin_theta_i = f64::sqrt(f64::max(1. - wi.z() * wi.z(), 0.));
+
+		let cos_phi = if sin_theta_o > 1e-4 && sin_theta_i > 1e-4 {
+			f64::max((wo.x() * wi.x() + wo.y() * wi.y()) / (sin_theta_o * sin_theta_i), 0.)
+		}
+		else {
+			0.
+		};
+
+		// alpha is the larger of the two polar angles and beta the smaller
+		let (sin_alpha, tan_beta) = if wi.z().abs() > wo.z().abs() {
+			(sin_theta_o, sin_theta_i / wi.z().abs())
+		}
+		else {
+			(sin_theta_i, sin_theta_o / f64::max(wo.z().abs(), 1e-4))
+		};
+
+		// Cosine sampling cancels the cosine and 1 / pi of the BRDF
+		let weight = self.a + self.b * cos_phi * sin_alpha * tan_beta;
+
+		Some((ray.spawn(rec.point, onb.local(&wi)), weight * self.albedo.value(rec.u, rec.v, &rec.point)))
+	}
+}
+
+impl OrenNayar {
+	pub fn new(albedo: Color, sigma: f64) -> OrenNayar {
+		OrenNayar::from_texture(Arc::new(SolidColor::new(albedo)), sigma)
+	}
+
+	pub fn from_texture(albedo: Arc<dyn Texture>, sigma: f64) -> OrenNayar {
+		let sigma = sigma.to_radians();
+		let sigma2 = sigma * sigma;
+
+		OrenNayar {
+			albedo,
+			a: 1. - sigma2 / (2. * (sigma2 + 0.33)),
+			b: 0.45 * sigma2 / (sigma2 + 0.09),
+			two_sided: true
+		}
+	}
+
+	// One sided surfaces absorb everything reaching their back face
+	pub fn two_sided(mut self, two_sided: bool) -> OrenNayar {
+		self.two_sided = two_sided;
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::oren_nayar::OrenNayar;
+	use crate::lambertian::Lambertian;
+	use crate::material::Material;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	// Attenuations, and the mean cosine of the scattered directions
+	fn scatter(material: &dyn Material, samples: usize) -> (Vec<Color>, f64) {
+		let ray = Ray::new(Point3::new(1., 0., 1.), Vec3::new(-1., 0., -1.));
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., material);
+		record.normal = Vec3::new(0., 0., 1.);
+		record.front_face = true;
+
+		let scattered = (0..samples).map(|_| material.scatter(&ray, &record).unwrap()).collect::<Vec<_>>();
+		let mean_cosine = scattered.iter().map(|(ray, _)| ray.direction().unit().z()).sum::<f64>() / samples as f64;
+
+		(scattered.into_iter().map(|(_, attenuation)| attenuation).collect(), mean_cosine)
+	}
+
+	#[test]
+	fn smooth_is_lambertian() {
+		let albedo = Color::new(0.3, 0.5, 0.7);
+		let (oren_nayar, oren_nayar_cosine) = scatter(&OrenNayar::new(albedo, 0.), 20000);
+		let (_, lambertian_cosine) = scatter(&Lambertian::new(albedo), 20000);
+
+		assert!(oren_nayar.iter().all(|attenuation| (*attenuation - albedo).length() < 1e-12));
+		// Both are cosine distributed, whose mean cosine is 2/3
+		assert!(f64::abs(oren_nayar_cosine - 2. / 3.) < 0.01, "{}", oren_nayar_cosine);
+		assert!(f64::abs(lambertian_cosine - 2. / 3.) < 0.01, "{}", lambertian_cosine);
+	}
+}
\ No newline at end of file
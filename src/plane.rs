@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+
+// Infinite plane through `point`, UVs repeat every unit along two tangent axes
+pub struct Plane {
+	point: Point3,
+	normal: Vec3,
+	tangent: Vec3,
+	bitangent: Vec3,
+	material: Arc<dyn Material>
+}
+
+impl Hittable for Plane {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let denom = self.normal.dot(ray.direction());
+		if f64::abs(denom) < 1e-8 {
+			return None;
+		}
+
+		let root = self.normal.dot(self.point - ray.origin()) / denom;
+		if !t.surround(root) {
+			return None;
+		}
+
+		let hit_point = ray.at(root);
+		let planar_hitpt = hit_point - self.point;
+
+		let mut record = HitRecord::new(hit_point, root, self.material.as_ref());
+		record.set_face_normal(ray, &self.normal);
+		record.u = self.tangent.dot(planar_hitpt).rem_euclid(1.);
+		record.v = self.bitangent.dot(planar_hitpt).rem_euclid(1.);
+		record.dpdu = self.tangent;
+		record.dpdv = self.bitangent;
+
+		Some(record)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		Aabb::universe()
+	}
+}
+
+impl Plane {
+	pub fn new(point: Point3, normal: Vec3, material: Arc<dyn Material>) -> Plane {
+		let normal = normal.unit();
+		let helper = if f64::abs(normal.x()) > 0.9 { Vec3::new(0., 1., 0.) } else { Vec3::new(1., 0., 0.) };
+		let tangent = helper.cross(normal).unit();
+
+		Plane {
+			point,
+			normal,
+			tangent,
+			bitangent: normal.cross(tangent),
+			material
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::plane::Plane;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn hit() {
+		let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+		let plane = Plane::new(Point3::new(0., 1., 0.), Vec3::new(0., 2., 0.), material);
+		let t = Interval::new(0.001, f64::MAX);
+
+		let record = plane.hit(&Ray::new(Point3::new(3.25, 5., -7.5), Vec3::new(0., -1., 0.)), &t).unwrap();
+		assert!(f64::abs(record.t - 4.) < 1e-9);
+		assert!((record.normal - Vec3::new(0., 1., 0.)).length() < 1e-9);
+		assert!(record.front_face);
+		assert!((0. ..1.).contains(&record.u) && (0. ..1.).contains(&record.v));
+		// Tangents are x and z in some order, so the UVs are the fractional parts of the coordinates
+		let mut uv = [record.u, record.v];
+		uv.sort_by(f64::total_cmp);
+		assert!(f64::abs(uv[0] - 0.25) < 1e-9 && f64::abs(uv[1] - 0.5) < 1e-9);
+
+		assert!(plane.hit(&Ray::new(Point3::new(0., 5., 0.), Vec3::new(1., 0., 0.)), &t).is_none());
+		assert!(plane.hit(&Ray::new(Point3::new(0., 5., 0.), Vec3::new(0., 1., 0.)), &t).is_none());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,171 @@
+use std::f64::consts::PI;
+
+const EPS: f64 = 1e-9;
+
+fn is_zero(x: f64) -> bool {
+	f64::abs(x) < EPS
+}
+
+// Real roots of a x² + b x + c, sorted
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+	if is_zero(a) {
+		if is_zero(b) {
+			return Vec::new();
+		}
+
+		return vec![-c / b];
+	}
+
+	let discriminant = b * b - 4. * a * c;
+	if discriminant < 0. {
+		return Vec::new();
+	}
+
+	// Avoids the cancellation of -b + sqrt(discriminant) when b is large
+	let q = -0.5 * (b + f64::copysign(f64::sqrt(discriminant), b));
+	let mut roots = if is_zero(q) { vec![0., 0.] } else { vec![q / a, c / q] };
+	roots.sort_by(f64::total_cmp);
+
+	roots
+}
+
+// Real roots of x³ + a x² + b x + c, sorted
+pub fn solve_normed_cubic(a: f64, b: f64, c: f64) -> Vec<f64> {
+	let sq_a = a * a;
+	let p = (-sq_a / 3. + b) / 3.;
+	let q = (2. / 27. * a * sq_a - a * b / 3. + c) / 2.;
+
+	let cb_p = p * p * p;
+	let d = q * q + cb_p;
+
+	// Tested relative to its terms, small coefficients would otherwise all look like a double root
+	let mut roots = if f64::abs(d) <= EPS * (q * q + f64::abs(cb_p)) {
+		if q == 0. {
+			vec![0.]
+		}
+		else {
+			let u = f64::cbrt(-q);
+			vec![2. * u, -u]
+		}
+	}
+	else if d < 0. {
+		let phi = f64::acos(f64::clamp(-q / f64::sqrt(-cb_p), -1., 1.)) / 3.;
+		let t = 2. * f64::sqrt(-p);
+
+		vec![t * f64::cos(phi), -t * f64::cos(phi + PI / 3.), -t * f64::cos(phi - PI / 3.)]
+	}
+	else {
+		let sqrt_d = f64::sqrt(d);
+		vec![f64::cbrt(sqrt_d - q) - f64::cbrt(sqrt_d + q)]
+	};
+
+	for root in roots.iter_mut() {
+		*root -= a / 3.;
+	}
+	roots.sort_by(f64::total_cmp);
+
+	roots
+}
+
+// Real roots of a x⁴ + b x³ + c x² + d x + e, sorted (Ferrari's method)
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+	if is_zero(a) {
+		return solve_normed_cubic_or_lower(b, c, d, e);
+	}
+
+	let (a3, a2, a1, a0) = (b / a, c / a, d / a, e / a);
+
+	// Depressed quartic y⁴ + p y² + q y + r with x = y - a3 / 4
+	let sq_a = a3 * a3;
+	let p = -3. / 8. * sq_a + a2;
+	let q = sq_a * a3 / 8. - a3 * a2 / 2. + a1;
+	let r = -3. / 256. * sq_a * sq_a + sq_a * a2 / 16. - a3 * a1 / 4. + a0;
+
+	let mut roots = if is_zero(r) {
+		let mut roots = solve_normed_cubic(0., p, q);
+		roots.push(0.);
+		roots
+	}
+	else {
+		// Any resolvent root with 2 z - p >= 0 factors the quartic, the largest always is one
+		let Some(&z) = solve_normed_cubic(-p / 2., -r, r * p / 2. - q * q / 8.).last() else {
+			return Vec::new();
+		};
+
+		// Both are positive for that root, rounding can only take them slightly below zero
+		let v = f64::sqrt(f64::max(2. * z - p, 0.));
+		let u = f64::sqrt(f64::max(z * z - r, 0.));
+
+		let mut roots = solve_quadratic(1., if q < 0. { -v } else { v }, z - u);
+		roots.extend(solve_quadratic(1., if q < 0. { v } else { -v }, z + u));
+		roots
+	};
+
+	// A few Newton steps on the original polynomial recover the precision lost in the resolvent
+	for root in roots.iter_mut() {
+		*root -= a3 / 4.;
+		for _ in 0..2 {
+			let x = *root;
+			let value = (((x + a3) * x + a2) * x + a1) * x + a0;
+			let derivative = ((4. * x + 3. * a3) * x + 2. * a2) * x + a1;
+			if !is_zero(derivative) {
+				*root = x - value / derivative;
+			}
+		}
+	}
+	roots.sort_by(f64::total_cmp);
+
+	roots
+}
+
+fn solve_normed_cubic_or_lower(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+	if is_zero(a) {
+		return solve_quadratic(b, c, d);
+	}
+
+	solve_normed_cubic(b / a, c / a, d / a)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::polynomial::{solve_quadratic, solve_normed_cubic, solve_quartic};
+
+	fn assert_roots(roots: Vec<f64>, expected: &[f64]) {
+		assert_eq!(roots.len(), expected.len(), "{:?}", roots);
+		for (root, expected) in roots.iter().zip(expected) {
+			assert!(f64::abs(root - expected) < 1e-6, "{:?} != {:?}", roots, expected);
+		}
+	}
+
+	#[test]
+	fn quadratic() {
+		assert_roots(solve_quadratic(1., -3., 2.), &[1., 2.]);
+		assert_roots(solve_quadratic(1., 0., 1.), &[]);
+	}
+
+	#[test]
+	fn cubic() {
+		assert_roots(solve_normed_cubic(-6., 11., -6.), &[1., 2., 3.]);
+		// Small coefficients, with a single real root
+		let (p, q, r) = (-0.1139745, -0.0010340, -0.0006357);
+		let roots = solve_normed_cubic(-p / 2., -r, r * p / 2. - q * q / 8.);
+		assert_eq!(roots.len(), 1, "{:?}", roots);
+		assert!(f64::abs(((roots[0] - p / 2.) * roots[0] - r) * roots[0] + r * p / 2. - q * q / 8.) < 1e-15);
+	}
+
+	#[test]
+	fn quartic() {
+		assert_roots(solve_quartic(1., -10., 35., -50., 24.), &[1., 2., 3., 4.]);
+		assert_roots(solve_quartic(2., 0., -2., 0., 0.), &[-1., 0., 0., 1.]);
+		assert_roots(solve_quartic(1., 0., 0., 0., 1.), &[]);
+
+		// Two real roots and a complex pair, whose smallest resolvent root doesn't factor the quartic
+		let (s1, p1) = (2.2822 + 2.2040, 2.2822 * 2.2040);
+		let (s2, p2) = (2. * 2.6904, 2.6904 * 2.6904 + 0.2425 * 0.2425);
+		assert_roots(solve_quartic(1., s1 + s2, p1 + p2 + s1 * s2, s1 * p2 + s2 * p1, p1 * p2), &[-2.2822, -2.2040]);
+
+		let (s1, p1) = (2.3749547 + 3.0656197, 2.3749547 * 3.0656197);
+		let (s2, p2) = (2. * 2.7285875, 2.7285875 * 2.7285875 + 0.0729004 * 0.0729004);
+		assert_roots(solve_quartic(1., s1 + s2, p1 + p2 + s1 * s2, s1 * p2 + s2 * p1, p1 * p2), &[-3.0656197, -2.3749547]);
+	}
+}
\ No newline at end of file
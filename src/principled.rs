@@ -0,0 +1,400 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+use crate::microfacet::Ggx;
+use crate::onb::Onb;
+use crate::fresnel;
+
+use rand::Rng;
+
+// Disney style uber material (Burley 2012, 2015), every parameter is a texture and
+// scalar parameters read the first channel of theirs
+pub struct Principled {
+	base_color: Arc<dyn Texture>,
+	metallic: Arc<dyn Texture>,
+	roughness: Arc<dyn Texture>,
+	specular: Arc<dyn Texture>,
+	specular_tint: Arc<dyn Texture>,
+	sheen: Arc<dyn Texture>,
+	clearcoat: Arc<dyn Texture>,
+	clearcoat_roughness: Arc<dyn Texture>,
+	transmission: Arc<dyn Texture>,
+	ior: Arc<dyn Texture>
+}
+
+// Parameters evaluated at a hit point
+struct Lobes {
+	base_color: Color,
+	metallic: f64,
+	roughness: f64,
+	specular_color: Color,
+	sheen: f64,
+	clearcoat: f64,
+	transmission: f64,
+	ior: f64,
+	specular: Ggx,
+	clearcoat_distribution: Ggx
+}
+
+impl Material for Principled {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		let lobes = self.lobes(rec);
+		let onb = Onb::new(&rec.normal);
+		let wo = onb.to_local(&-ray.direction().unit());
+		if wo.z() <= 0. {
+			return None;
+		}
+
+		// Lobes are picked proportionally to a rough estimate of their contribution
+		let diffuse_weight = (1. - lobes.metallic) * (1. - lobes.transmission);
+		let transmission_weight = (1. - lobes.metallic) * lobes.transmission;
+		let specular_weight = 1. - transmission_weight;
+		let clearcoat_weight = 0.25 * lobes.clearcoat;
+
+		let probabilities = [
+			diffuse_weight * luminance(&lobes.base_color),
+			specular_weight * luminance(&schlick(&lobes.specular_color, wo.z())),
+			transmission_weight,
+			clearcoat_weight * schlick_scalar(0.04, wo.z())
+		];
+		let total: f64 = probabilities.iter().sum();
+		if total <= 0. {
+			return None;
+		}
+
+		let lobe = pick_lobe(&probabilities, rand::thread_rng().gen());
+		let probability = probabilities[lobe] / total;
+
+		let (wi, weight) = match lobe {
+			0 => Principled::sample_diffuse(&lobes, &wo, diffuse_weight)?,
+			1 => Principled::sample_specular(&lobes.specular, &wo, |cos| schlick(&lobes.specular_color, cos) * specular_weight)?,
+			2 => Principled::sample_transmission(&lobes, &wo, rec.front_face, transmission_weight)?,
+			_ => Principled::sample_specular(&lobes.clearcoat_distribution, &wo, |cos| {
+				let f = clearcoat_weight * schlick_scalar(0.04, cos);
+				Color::new(f, f, f)
+			})?
+		};
+
+		Some((ray.spawn(rec.point, onb.local(&wi)), weight / probability))
+	}
+}
+
+impl Principled {
+	pub fn new(base_color: Color) -> Principled {
+		Principled::from_texture(Arc::new(SolidColor::new(base_color)))
+	}
+
+	pub fn from_texture(base_color: Arc<dyn Texture>) -> Principled {
+		Principled {
+			base_color,
+			metallic: constant(0.),
+			roughness: constant(0.5),
+			specular: constant(0.5),
+			specular_tint: constant(0.),
+			sheen: constant(0.),
+			clearcoat: constant(0.),
+			clearcoat_roughness: constant(0.1),
+			transmission: constant(0.),
+			ior: constant(1.5)
+		}
+	}
+
+	pub fn metallic(self, metallic: f64) -> Principled {
+		self.metallic_texture(constant(metallic))
+	}
+
+	pub fn metallic_texture(mut self, metallic: Arc<dyn Texture>) -> Principled {
+		self.metallic = metallic;
+		self
+	}
+
+	pub fn roughness(self, roughness: f64) -> Principled {
+		self.roughness_texture(constant(roughness))
+	}
+
+	pub fn roughness_texture(mut self, roughness: Arc<dyn Texture>) -> Principled {
+		self.roughness = roughness;
+		self
+	}
+
+	// Dielectric reflectance at normal incidence remapped to [0, 0.08], 0.5 matches an IOR of 1.5
+	pub fn specular(self, specular: f64) -> Principled {
+		self.specular_texture(constant(specular))
+	}
+
+	pub fn specular_texture(mut self, specular: Arc<dyn Texture>) -> Principled {
+		self.specular = specular;
+		self
+	}
+
+	// Tints the dielectric specular towards the hue of the base color
+	pub fn specular_tint(self, specular_tint: f64) -> Principled {
+		self.specular_tint_texture(constant(specular_tint))
+	}
+
+	pub fn specular_tint_texture(mut self, specular_tint: Arc<dyn Texture>) -> Principled {
+		self.specular_tint = specular_tint;
+		self
+	}
+
+	// Extra grazing retro-reflection for cloth
+	pub fn sheen(self, sheen: f64) -> Principled {
+		self.sheen_texture(constant(sheen))
+	}
+
+	pub fn sheen_texture(mut self, sheen: Arc<dyn Texture>) -> Principled {
+		self.sheen = sheen;
+		self
+	}
+
+	// Second, colourless specular lobe with an IOR of 1.5
+	pub fn clearcoat(self, clearcoat: f64) -> Principled {
+		self.clearcoat_texture(constant(clearcoat))
+	}
+
+	pub fn clearcoat_texture(mut self, clearcoat: Arc<dyn Texture>) -> Principled {
+		self.clearcoat = clearcoat;
+		self
+	}
+
+	pub fn clearcoat_roughness(self, clearcoat_roughness: f64) -> Principled {
+		self.clearcoat_roughness_texture(constant(clearcoat_roughness))
+	}
+
+	pub fn clearcoat_roughness_texture(mut self, clearcoat_roughness: Arc<dyn Texture>) -> Principled {
+		self.clearcoat_roughness = clearcoat_roughness;
+		self
+	}
+
+	// Fraction of the dielectric base that refracts instead of being diffuse
+	pub fn transmission(self, transmission: f64) -> Principled {
+		self.transmission_texture(constant(transmission))
+	}
+
+	pub fn transmission_texture(mut self, transmission: Arc<dyn Texture>) -> Principled {
+		self.transmission = transmission;
+		self
+	}
+
+	pub fn ior(self, ior: f64) -> Principled {
+		self.ior_texture(constant(ior))
+	}
+
+	pub fn ior_texture(mut self, ior: Arc<dyn Texture>) -> Principled {
+		self.ior = ior;
+		self
+	}
+
+	// Sets a parameter from its name, as in material files. None if there is no such parameter
+	pub fn parameter(self, name: &str, value: Arc<dyn Texture>) -> Option<Principled> {
+		Some(match name {
+			"base_color" => Principled { base_color: value, ..self },
+			"metallic" => self.metallic_texture(value),
+			"roughness" => self.roughness_texture(value),
+			"specular" => self.specular_texture(value),
+			"specular_tint" => self.specular_tint_texture(value),
+			"sheen" => self.sheen_texture(value),
+			"clearcoat" => self.clearcoat_texture(value),
+			"clearcoat_roughness" => self.clearcoat_roughness_texture(value),
+			"transmission" => self.transmission_texture(value),
+			"ior" => self.ior_texture(value),
+			_ => return None
+		})
+	}
+
+	fn lobes(&self, rec: &HitRecord) -> Lobes {
+		let value = |texture: &Arc<dyn Texture>| texture.value(rec.u, rec.v, &rec.point);
+		let scalar = |texture: &Arc<dyn Texture>| f64::clamp(value(texture).x(), 0., 1.);
+
+		let base_color = value(&self.base_color);
+		let metallic = scalar(&self.metallic);
+		let roughness = scalar(&self.roughness);
+		let clearcoat_roughness = scalar(&self.clearcoat_roughness);
+
+		let white = Color::new(1., 1., 1.);
+		let tint = tint(&base_color);
+		let dielectric_color = 0.08 * scalar(&self.specular) * lerp(&white, &tint, scalar(&self.specular_tint));
+
+		Lobes {
+			base_color,
+			metallic,
+			roughness,
+			specular_color: lerp(&dielectric_color, &base_color, metallic),
+			sheen: scalar(&self.sheen),
+			clearcoat: scalar(&self.clearcoat),
+			transmission: scalar(&self.transmission),
+			ior: f64::max(value(&self.ior).x(), 1.),
+			specular: Ggx::from_roughness(roughness, roughness),
+			clearcoat_distribution: Ggx::from_roughness(clearcoat_roughness, clearcoat_roughness)
+		}
+	}
+
+	// Burley's retro-reflective diffuse plus sheen, both sampled with a cosine distribution
+	fn sample_diffuse(lobes: &Lobes, wo: &Vec3, weight: f64) -> Option<(Vec3, Color)> {
+		let wi = Vec3::random_cosine_direction();
+		if wi.z() <= 0. {
+			return None;
+		}
+
+		let h = (*wo + wi).unit();
+		let cos_d = wi.dot(h);
+		let f_d90 = 0.5 + 2. * lobes.roughness * cos_d * cos_d;
+		let retro = (1. + (f_d90 - 1.) * f64::powi(1. - wi.z(), 5)) * (1. + (f_d90 - 1.) * f64::powi(1. - wo.z(), 5));
+
+		let sheen_color = 0.5 * (Color::new(1., 1., 1.) + tint(&lobes.base_color));
+		let sheen = PI * lobes.sheen * f64::powi(1. - cos_d, 5) * sheen_color;
+
+		Some((wi, weight * (retro * lobes.base_color + sheen)))
+	}
+
+	// Visible normal sampling of a GGX lobe, leaving F * G2 / G1 as the weight
+	fn sample_specular<F>(distribution: &Ggx, wo: &Vec3, fresnel: F) -> Option<(Vec3, Color)>
+	where
+		F: Fn(f64) -> Color
+	{
+		let m = sample_normal(distribution, wo);
+		let wi = 2. * wo.dot(m) * m - *wo;
+		if wi.z() <= 0. {
+			return None;
+		}
+
+		let shadowing = if distribution.is_smooth() { 1. } else { distribution.g2(wo, &wi) / distribution.g1(wo) };
+
+		Some((wi, fresnel(wo.dot(m)) * shadowing))
+	}
+
+	// Rough dielectric interface, refracted light is tinted by the base color
+	fn sample_transmission(lobes: &Lobes, wo: &Vec3, front_face: bool, weight: f64) -> Option<(Vec3, Color)> {
+		let refraction_ratio = if front_face { 1. / lobes.ior } else { lobes.ior };
+		let m = sample_normal(&lobes.specular, wo);
+		let reflectance = fresnel::dielectric(wo.dot(m), 1. / refraction_ratio);
+
+		let (wi, refracted) = if rand::thread_rng().gen::<f64>() < reflectance {
+			(2. * wo.dot(m) * m - *wo, false)
+		}
+		else {
+			((-*wo).refract(&m, refraction_ratio), true)
+		};
+		if (wi.z() > 0.) == refracted {
+			return None;
+		}
+
+		let shadowing = if lobes.specular.is_smooth() { 1. } else { lobes.specular.g2(wo, &wi) / lobes.specular.g1(wo) };
+		let color = if refracted { lobes.base_color } else { Color::new(1., 1., 1.) };
+
+		Some((wi, weight * shadowing * color))
+	}
+}
+
+// Lobe whose share of the total `u` in [0, 1) falls in. Rounding can leave `u` past the last share, it then
+// goes to the last lobe that can be picked rather than one that can't
+fn pick_lobe(probabilities: &[f64; 4], u: f64) -> usize {
+	let mut pick = u * probabilities.iter().sum::<f64>();
+
+	probabilities.iter()
+		.position(|p| { pick -= p; pick < 0. })
+		.or_else(|| probabilities.iter().rposition(|p| *p > 0.))
+		.unwrap_or(0)
+}
+
+fn constant(value: f64) -> Arc<dyn Texture> {
+	Arc::new(SolidColor::new(Color::new(value, value, value)))
+}
+
+fn sample_normal(distribution: &Ggx, wo: &Vec3) -> Vec3 {
+	if distribution.is_smooth() {
+		return Vec3::new(0., 0., 1.);
+	}
+
+	let mut rng = rand::thread_rng();
+	distribution.sample_visible_normal(wo, rng.gen(), rng.gen())
+}
+
+fn luminance(color: &Color) -> f64 {
+	0.2126 * color.x() + 0.7152 * color.y() + 0.0722 * color.z()
+}
+
+// Hue and saturation of a color with its luminance normalised away
+fn tint(color: &Color) -> Color {
+	let luminance = luminance(color);
+	if luminance > 0. { *color / luminance } else { Color::new(1., 1., 1.) }
+}
+
+fn lerp(a: &Color, b: &Color, t: f64) -> Color {
+	(1. - t) * *a + t * *b
+}
+
+fn schlick(f0: &Color, cosine: f64) -> Color {
+	*f0 + (Color::new(1., 1., 1.) - *f0) * f64::powi(1. - cosine, 5)
+}
+
+fn schlick_scalar(f0: f64, cosine: f64) -> f64 {
+	f0 + (1. - f0) * f64::powi(1. - cosine, 5)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::principled::{Principled, pick_lobe};
+	use crate::material::Material;
+	use crate::hit_record::HitRecord;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn lobe_selection() {
+		let probabilities = [0.25, 0., 0.75, 0.];
+
+		assert_eq!(pick_lobe(&probabilities, 0.), 0);
+		assert_eq!(pick_lobe(&probabilities, 0.2), 0);
+		assert_eq!(pick_lobe(&probabilities, 0.3), 2);
+		// Past the end, never the lobes that can't be picked
+		assert_eq!(pick_lobe(&probabilities, 1.), 2);
+		assert_eq!(pick_lobe(&[0., 0., 1., 0.], 1.), 2);
+		assert_eq!(pick_lobe(&[0.1, 0.2, 0.3, 0.4], 0.5), 2);
+	}
+
+	// Average throughput of rays arriving along the normal
+	fn albedo(material: &Principled) -> f64 {
+		let ray = Ray::new(Point3::new(0., 0., 1.), Vec3::new(0., 0., -1.));
+		let mut record = HitRecord::new(Point3::new(0., 0., 0.), 1., material);
+		record.set_face_normal(&ray, &Vec3::new(0., 0., 1.));
+
+		let samples = 20000;
+		let mut total = 0.;
+		for _ in 0..samples {
+			if let Some((_, weight)) = material.scatter(&ray, &record) {
+				assert!(weight.x().is_finite() && weight.y().is_finite() && weight.z().is_finite(), "{:?}", weight);
+				total += weight.y();
+			}
+		}
+
+		total / f64::from(samples)
+	}
+
+	#[test]
+	fn energy() {
+		let white = Color::new(1., 1., 1.);
+
+		assert!(f64::abs(albedo(&Principled::new(white).metallic(1.).roughness(0.)) - 1.) < 1e-9);
+		// Only the transmission lobe can be picked
+		assert!(f64::abs(albedo(&Principled::new(white).transmission(1.).roughness(0.)) - 1.) < 1e-9);
+
+		for material in [
+			Principled::new(white),
+			Principled::new(white).metallic(1.).roughness(0.5),
+			Principled::new(white).transmission(1.).roughness(0.5),
+			Principled::new(white).clearcoat(1.).sheen(1.)
+		] {
+			let albedo = albedo(&material);
+			assert!(albedo > 0.8 && albedo < 1.1, "{}", albedo);
+		}
+	}
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+use std::f64::consts::PI;
+
+use crate::vec3::Vec3;
+
+// How the camera maps image points to rays, all share the orientation given by lookfrom, lookat and up
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Projection {
+	// Thin lens with `fov` as the vertical field of view
+	#[default]
+	Perspective,
+	// Parallel rays, the image covers the perspective viewport at `focus_dist`
+	Orthographic,
+	// Equidistant fisheye, `fov` is the angle covered by the image circle fitted to the shorter side
+	Fisheye,
+	// Full 360 by 180 degree panorama, longitude along the width starting behind the camera
+	Equirectangular,
+	// Six 90 degree faces laid out 3 by 2 as +x, -x, +y / -y, +z, -z of the camera frame, where -z looks
+	// at `lookat`. Faces are seen from the inside with +y up, the top and bottom ones continue the front one
+	CubeMap
+}
+
+impl Projection {
+	// Panoramic layouts only make sense at a fixed image shape
+	pub fn aspect_ratio(&self) -> Option<f64> {
+		match self {
+			Projection::Equirectangular => Some(2.),
+			Projection::CubeMap => Some(1.5),
+			_ => None
+		}
+	}
+
+	// Direction in the camera frame (x right, y up, looking down -z) through the image point (x, y) in [0, 1],
+	// y going down. None outside of the fisheye circle. Planar projections are traced through the camera's
+	// viewport instead and only get the view axis
+	pub fn direction(&self, x: f64, y: f64, fov: f64, aspect_ratio: f64) -> Option<Vec3> {
+		match self {
+			Projection::Perspective | Projection::Orthographic => Some(Vec3::new(0., 0., -1.)),
+			Projection::Fisheye => {
+				let px = (2. * x - 1.) * f64::max(aspect_ratio, 1.);
+				let py = (1. - 2. * y) * f64::max(1. / aspect_ratio, 1.);
+				let r = f64::sqrt(px * px + py * py);
+				if r > 1. {
+					return None;
+				}
+
+				let theta = r * fov.to_radians() / 2.;
+				let phi = f64::atan2(py, px);
+
+				Some(Vec3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), -theta.cos()))
+			},
+			Projection::Equirectangular => {
+				let phi = (x - 0.5) * 2. * PI;
+				let latitude = (0.5 - y) * PI;
+
+				Some(Vec3::new(latitude.cos() * phi.sin(), latitude.sin(), -latitude.cos() * phi.cos()))
+			},
+			Projection::CubeMap => {
+				let column = f64::min(f64::floor(3. * x), 2.);
+				let row = f64::min(f64::floor(2. * y), 1.);
+				let s = 2. * (3. * x - column) - 1.;
+				let t = 2. * (2. * y - row) - 1.;
+
+				let direction = match (row as usize, column as usize) {
+					(0, 0) => Vec3::new(1., -t, s),
+					(0, 1) => Vec3::new(-1., -t, -s),
+					(0, _) => Vec3::new(s, 1., -t),
+					(_, 0) => Vec3::new(s, -1., t),
+					(_, 1) => Vec3::new(-s, -t, 1.),
+					_ => Vec3::new(s, -t, -1.)
+				};
+
+				Some(direction.unit())
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::projection::Projection;
+	use crate::vec3::Vec3;
+
+	fn assert_near(a: Vec3, b: Vec3) {
+		assert!((a - b).length() < 1e-9, "{:?} != {:?}", a, b);
+	}
+
+	#[test]
+	fn centers_look_forward() {
+		let forward = Vec3::new(0., 0., -1.);
+
+		assert_near(Projection::Fisheye.direction(0.5, 0.5, 180., 1.).unwrap(), forward);
+		assert_near(Projection::Equirectangular.direction(0.5, 0.5, 0., 2.).unwrap(), forward);
+		assert_near(Projection::CubeMap.direction(5. / 6., 0.75, 0., 1.5).unwrap(), forward);
+	}
+
+	#[test]
+	fn fisheye_edge() {
+		let edge = Projection::Fisheye.direction(1., 0.5, 180., 1.).unwrap();
+
+		assert_near(edge, Vec3::new(1., 0., 0.));
+		assert!(Projection::Fisheye.direction(0., 0., 180., 1.).is_none());
+	}
+
+	#[test]
+	fn equirectangular() {
+		assert_near(Projection::Equirectangular.direction(0.75, 0.5, 0., 2.).unwrap(), Vec3::new(1., 0., 0.));
+		assert_near(Projection::Equirectangular.direction(0.3, 0., 0., 2.).unwrap(), Vec3::new(0., 1., 0.));
+	}
+
+	#[test]
+	fn cube_faces_are_continuous() {
+		// Right edge of the front face meets the left edge of the +x face, top edge meets the bottom of +y
+		let front_right = Projection::CubeMap.direction(1. - 1e-12, 0.75, 0., 1.5).unwrap();
+		let right_left = Projection::CubeMap.direction(0., 0.25, 0., 1.5).unwrap();
+		assert_near(front_right, right_left);
+
+		let front_top = Projection::CubeMap.direction(5. / 6., 0.5, 0., 1.5).unwrap();
+		let up_bottom = Projection::CubeMap.direction(5. / 6., 0.5 - 1e-12, 0., 1.5).unwrap();
+		assert_near(front_top, up_bottom);
+	}
+}
\ No newline at end of file
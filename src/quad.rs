@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+
+use rand::Rng;
+
+// Parallelogram starting at corner `q` with edges `u` and `v`
+pub struct Quad {
+	q: Point3,
+	u: Vec3,
+	v: Vec3,
+	w: Vec3,
+	normal: Vec3,
+	d: f64,
+	area: f64,
+	material: Arc<dyn Material>,
+	bbox: Aabb
+}
+
+impl Hittable for Quad {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let denom = self.normal.dot(ray.direction());
+		if f64::abs(denom) < 1e-8 {
+			return None;
+		}
+
+		let root = (self.d - self.normal.dot(ray.origin())) / denom;
+		if !t.surround(root) {
+			return None;
+		}
+
+		let hit_point = ray.at(root);
+		let planar_hitpt = hit_point - self.q;
+		let alpha = self.w.dot(planar_hitpt.cross(self.v));
+		let beta = self.w.dot(self.u.cross(planar_hitpt));
+
+		let unit = Interval::new(0., 1.);
+		if !unit.contains(alpha) || !unit.contains(beta) {
+			return None;
+		}
+
+		let mut record = HitRecord::new(hit_point, root, self.material.as_ref());
+		record.set_face_normal(ray, &self.normal);
+		record.u = alpha;
+		record.v = beta;
+		record.dpdu = self.u;
+		record.dpdv = self.v;
+
+		Some(record)
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+
+	fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+		let Some(record) = self.hit(&Ray::new(*origin, *direction), &Interval::new(0.001, f64::MAX)) else {
+			return 0.;
+		};
+
+		let distance_squared = record.t * record.t * direction.squared_length();
+		let cosine = f64::abs(direction.dot(record.normal) / direction.length());
+
+		distance_squared / (cosine * self.area)
+	}
+
+	fn random(&self, origin: &Point3) -> Vec3 {
+		let mut rng = rand::thread_rng();
+		let p = self.q + (rng.gen::<f64>() * self.u) + (rng.gen::<f64>() * self.v);
+
+		p - *origin
+	}
+}
+
+impl Quad {
+	pub fn new(q: Point3, u: Vec3, v: Vec3, material: Arc<dyn Material>) -> Quad {
+		let n = u.cross(v);
+		let normal = n.unit();
+
+		let bbox_diagonal1 = Aabb::from_points(q, q + u + v);
+		let bbox_diagonal2 = Aabb::from_points(q + u, q + v);
+
+		Quad {
+			q,
+			u,
+			v,
+			w: n / n.dot(n),
+			normal,
+			d: normal.dot(q),
+			area: n.length(),
+			material,
+			bbox: Aabb::surrounding(&bbox_diagonal1, &bbox_diagonal2).pad()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::quad::Quad;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn quad() -> Quad {
+		let material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+
+		Quad::new(Point3::new(-1., -1., 0.), Vec3::new(2., 0., 0.), Vec3::new(0., 2., 0.), material)
+	}
+
+	#[test]
+	fn hit() {
+		let quad = quad();
+		let t = Interval::new(0.001, f64::MAX);
+
+		let record = quad.hit(&Ray::new(Point3::new(0.5, 0., 5.), Vec3::new(0., 0., -1.)), &t).unwrap();
+		assert!(f64::abs(record.t - 5.) < 1e-9);
+		assert!(f64::abs(record.u - 0.75) < 1e-9);
+		assert!(f64::abs(record.v - 0.5) < 1e-9);
+		assert!(record.front_face);
+		assert!((record.normal - Vec3::new(0., 0., 1.)).length() < 1e-9);
+
+		let back = quad.hit(&Ray::new(Point3::new(0.5, 0., -5.), Vec3::new(0., 0., 1.)), &t).unwrap();
+		assert!(!back.front_face);
+		assert!((back.normal - Vec3::new(0., 0., -1.)).length() < 1e-9);
+
+		assert!(quad.hit(&Ray::new(Point3::new(1.5, 0., 5.), Vec3::new(0., 0., -1.)), &t).is_none());
+	}
+
+	#[test]
+	fn area_sampling() {
+		let quad = quad();
+		let origin = Point3::new(0., 0., 5.);
+
+		assert!(f64::abs(quad.pdf_value(&origin, &Vec3::new(0., 0., -1.)) - 25. / 4.) < 1e-9);
+		assert_eq!(quad.pdf_value(&origin, &Vec3::new(0., 0., 1.)), 0.);
+		for _ in 0..100 {
+			assert!(quad.pdf_value(&origin, &quad.random(&origin)) > 0.);
+		}
+	}
+}
\ No newline at end of file
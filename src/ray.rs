@@ -1,18 +1,53 @@
 use crate::vec3::{Vec3, Point3};
+use crate::medium_stack::MediumStack;
+use crate::spectrum::Wavelengths;
 
+#[derive(Clone, Copy)]
 pub struct Ray {
 	origin: Point3,
-	direction: Vec3
+	direction: Vec3,
+	media: MediumStack,
+	wavelengths: Option<Wavelengths>,
+	time: f64
 }
 
 impl Ray {
 	pub fn new(origin: Vec3, direction: Point3) -> Ray {
 		Ray {
 			origin,
-			direction
+			direction,
+			media: MediumStack::new(),
+			wavelengths: None,
+			time: 0.
 		}
 	}
 
+	// Continues the path from a new origin and direction, keeping the state carried by the ray
+	pub fn spawn(&self, origin: Point3, direction: Vec3) -> Ray {
+		Ray {
+			origin,
+			direction,
+			..*self
+		}
+	}
+
+	pub fn with_media(mut self, media: MediumStack) -> Ray {
+		self.media = media;
+		self
+	}
+
+	// Wavelengths sampled for the path in spectral mode, RGB rendering leaves them unset
+	pub fn with_wavelengths(mut self, wavelengths: Wavelengths) -> Ray {
+		self.wavelengths = Some(wavelengths);
+		self
+	}
+
+	// Instant within the shutter interval the path is traced at, moving objects are placed accordingly
+	pub fn with_time(mut self, time: f64) -> Ray {
+		self.time = time;
+		self
+	}
+
 	pub fn at(&self, t: f64) -> Point3 {
 		self.origin + self.direction * t
 	}
@@ -24,4 +59,16 @@ impl Ray {
 	pub fn direction(&self) -> Vec3 {
 		self.direction
 	}
+
+	pub fn media(&self) -> MediumStack {
+		self.media
+	}
+
+	pub fn wavelengths(&self) -> Option<Wavelengths> {
+		self.wavelengths
+	}
+
+	pub fn time(&self) -> f64 {
+		self.time
+	}
 }
\ No newline at end of file
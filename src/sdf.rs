@@ -0,0 +1,199 @@
+use crate::vec3::{Vec3, Point3};
+use crate::interval::Interval;
+use crate::aabb::Aabb;
+
+// Signed distance functions, composed as a tree and evaluated around the origin
+pub enum Sdf {
+	Sphere { radius: f64 },
+	Box { half_extents: Vec3 },
+	RoundBox { half_extents: Vec3, radius: f64 },
+	Torus { major_radius: f64, minor_radius: f64 },
+	Translate { offset: Vec3, inner: Box<Sdf> },
+	SmoothUnion { a: Box<Sdf>, b: Box<Sdf>, k: f64 },
+	SmoothSubtract { base: Box<Sdf>, cut: Box<Sdf>, k: f64 },
+	Repeat { period: Vec3, inner: Box<Sdf> },
+	Twist { rate: f64, inner: Box<Sdf>, lipschitz: f64 }
+}
+
+impl Sdf {
+	pub fn sphere(radius: f64) -> Sdf {
+		Sdf::Sphere { radius }
+	}
+
+	pub fn cuboid(half_extents: Vec3) -> Sdf {
+		Sdf::Box { half_extents }
+	}
+
+	// `half_extents` is the outer size, edges are rounded by `radius` inside it
+	pub fn round_box(half_extents: Vec3, radius: f64) -> Sdf {
+		Sdf::RoundBox { half_extents, radius }
+	}
+
+	pub fn torus(major_radius: f64, minor_radius: f64) -> Sdf {
+		Sdf::Torus { major_radius, minor_radius }
+	}
+
+	pub fn translate(self, offset: Vec3) -> Sdf {
+		Sdf::Translate { offset, inner: Box::new(self) }
+	}
+
+	pub fn smooth_union(self, other: Sdf, k: f64) -> Sdf {
+		Sdf::SmoothUnion { a: Box::new(self), b: Box::new(other), k }
+	}
+
+	pub fn smooth_subtract(self, cut: Sdf, k: f64) -> Sdf {
+		Sdf::SmoothSubtract { base: Box::new(self), cut: Box::new(cut), k }
+	}
+
+	// Infinite repetition, a zero period leaves that axis alone
+	pub fn repeat(self, period: Vec3) -> Sdf {
+		Sdf::Repeat { period, inner: Box::new(self) }
+	}
+
+	// Rotates the xz plane by `rate` radians per unit along y
+	pub fn twist(self, rate: f64) -> Sdf {
+		// Twisting stretches space, the distance is shortened so the marching stays conservative
+		let radius = xz_radius(&self.bounding_box());
+		let lipschitz = f64::sqrt(1. + (rate * radius) * (rate * radius));
+
+		Sdf::Twist { rate, inner: Box::new(self), lipschitz }
+	}
+
+	pub fn distance(&self, p: &Point3) -> f64 {
+		match self {
+			Sdf::Sphere { radius } => p.length() - radius,
+			Sdf::Box { half_extents } => {
+				let q = abs(p) - *half_extents;
+
+				max(&q, 0.).length() + f64::min(max_component(&q), 0.)
+			},
+			Sdf::RoundBox { half_extents, radius } => {
+				let q = abs(p) - *half_extents + Vec3::new(*radius, *radius, *radius);
+
+				max(&q, 0.).length() + f64::min(max_component(&q), 0.) - radius
+			},
+			Sdf::Torus { major_radius, minor_radius } => {
+				let ring = f64::sqrt(p.x() * p.x() + p.z() * p.z()) - major_radius;
+
+				f64::sqrt(ring * ring + p.y() * p.y()) - minor_radius
+			},
+			Sdf::Translate { offset, inner } => inner.distance(&(*p - *offset)),
+			Sdf::SmoothUnion { a, b, k } => {
+				let (a, b) = (a.distance(p), b.distance(p));
+				let h = f64::clamp(0.5 + 0.5 * (b - a) / k, 0., 1.);
+
+				b + (a - b) * h - k * h * (1. - h)
+			},
+			Sdf::SmoothSubtract { base, cut, k } => {
+				let (base, cut) = (base.distance(p), cut.distance(p));
+				let h = f64::clamp(0.5 - 0.5 * (base + cut) / k, 0., 1.);
+
+				base + (-cut - base) * h + k * h * (1. - h)
+			},
+			Sdf::Repeat { period, inner } => {
+				let wrap = |x: f64, period: f64| if period > 0. { x - period * f64::round(x / period) } else { x };
+				let q = Point3::new(wrap(p.x(), period.x()), wrap(p.y(), period.y()), wrap(p.z(), period.z()));
+
+				inner.distance(&q)
+			},
+			Sdf::Twist { rate, inner, lipschitz } => {
+				let (sin, cos) = f64::sin_cos(rate * p.y());
+				let q = Point3::new(cos * p.x() - sin * p.z(), p.y(), sin * p.x() + cos * p.z());
+
+				inner.distance(&q) / lipschitz
+			}
+		}
+	}
+
+	pub fn bounding_box(&self) -> Aabb {
+		match self {
+			Sdf::Sphere { radius } => symmetric(Vec3::new(*radius, *radius, *radius)),
+			Sdf::Box { half_extents } | Sdf::RoundBox { half_extents, .. } => symmetric(*half_extents),
+			Sdf::Torus { major_radius, minor_radius } => {
+				let extent = major_radius + minor_radius;
+
+				symmetric(Vec3::new(extent, *minor_radius, extent))
+			},
+			Sdf::Translate { offset, inner } => {
+				let bbox = inner.bounding_box();
+
+				Aabb::from_points(bbox.min() + *offset, bbox.max() + *offset)
+			},
+			Sdf::SmoothUnion { a, b, k } => {
+				let bbox = Aabb::surrounding(&a.bounding_box(), &b.bounding_box());
+
+				Aabb::new(bbox.x.expand(*k), bbox.y.expand(*k), bbox.z.expand(*k))
+			},
+			Sdf::SmoothSubtract { base, .. } => base.bounding_box(),
+			Sdf::Repeat { period, inner } => {
+				let bbox = inner.bounding_box();
+				let axis = |interval: Interval, period: f64| if period > 0. { Interval::universe() } else { interval };
+
+				Aabb::new(axis(bbox.x, period.x()), axis(bbox.y, period.y()), axis(bbox.z, period.z()))
+			},
+			Sdf::Twist { inner, .. } => {
+				let bbox = inner.bounding_box();
+				let radius = xz_radius(&bbox);
+
+				Aabb::new(Interval::new(-radius, radius), bbox.y, Interval::new(-radius, radius))
+			}
+		}
+	}
+}
+
+fn symmetric(extent: Vec3) -> Aabb {
+	Aabb::from_points(-extent, extent)
+}
+
+// Radius of the cylinder around the y axis containing the box
+fn xz_radius(bbox: &Aabb) -> f64 {
+	f64::sqrt(
+		f64::max(bbox.x.min * bbox.x.min, bbox.x.max * bbox.x.max)
+		+ f64::max(bbox.z.min * bbox.z.min, bbox.z.max * bbox.z.max)
+	)
+}
+
+fn abs(v: &Vec3) -> Vec3 {
+	Vec3::new(f64::abs(v.x()), f64::abs(v.y()), f64::abs(v.z()))
+}
+
+fn max(v: &Vec3, x: f64) -> Vec3 {
+	Vec3::new(f64::max(v.x(), x), f64::max(v.y(), x), f64::max(v.z(), x))
+}
+
+fn max_component(v: &Vec3) -> f64 {
+	f64::max(v.x(), f64::max(v.y(), v.z()))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::sdf::Sdf;
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn distances() {
+		let cuboid = Sdf::cuboid(Vec3::new(1., 1., 1.));
+		assert!(f64::abs(cuboid.distance(&Point3::new(0.1, 0.2, 1.)) - 0.) < 1e-12);
+		assert!(f64::abs(cuboid.distance(&Point3::new(0.1, 0.2, 3.)) - 2.) < 1e-12);
+		assert!(f64::abs(cuboid.distance(&Point3::new(0., 0., 0.)) + 1.) < 1e-12);
+		assert!(f64::abs(cuboid.distance(&Point3::new(2., 2., 1.)) - f64::sqrt(2.)) < 1e-12);
+
+		let round_box = Sdf::round_box(Vec3::new(1., 1., 1.), 0.25);
+		assert!(f64::abs(round_box.distance(&Point3::new(0., 0., 2.)) - 1.) < 1e-12);
+		// Corners are rounded inside the outer size
+		assert!(round_box.distance(&Point3::new(1., 1., 1.)) > 0.);
+
+		let moved = Sdf::sphere(1.).translate(Vec3::new(2., 0., 0.));
+		assert!(f64::abs(moved.distance(&Point3::new(0., 0., 0.)) - 1.) < 1e-12);
+	}
+
+	#[test]
+	fn bounding_boxes() {
+		let bbox = Sdf::torus(2., 0.5).bounding_box();
+		assert_eq!((bbox.x.min, bbox.x.max, bbox.y.min, bbox.y.max), (-2.5, 2.5, -0.5, 0.5));
+
+		let twisted = Sdf::cuboid(Vec3::new(1., 2., 1.)).twist(1.).bounding_box();
+		assert!(f64::abs(twisted.x.max - f64::sqrt(2.)) < 1e-12);
+		assert_eq!((twisted.y.min, twisted.y.max), (-2., 2.));
+	}
+}
\ No newline at end of file
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::sdf::Sdf;
+
+// Sphere traces a signed distance function placed at `center`
+pub struct SdfShape {
+	sdf: Sdf,
+	center: Point3,
+	epsilon: f64,
+	max_steps: u32,
+	material: Arc<dyn Material>,
+	bbox: Aabb
+}
+
+impl Hittable for SdfShape {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let range = self.bbox.clip(ray, t)?;
+		let length = ray.direction().length();
+
+		// Rays travelling inside (refraction) march on the absolute distance, and rays starting on the
+		// surface must first leave it before they can hit anything. Those entering the bounding box
+		// from outside can't, but may enter right on the surface of box-like shapes
+		let mut root = range.min;
+		let mut left_surface = range.min > t.min;
+		for _ in 0..self.max_steps {
+			if root > range.max {
+				return None;
+			}
+
+			let distance = f64::abs(self.sdf.distance(&(ray.at(root) - self.center)));
+			left_surface |= distance >= self.epsilon;
+			if left_surface && distance < self.epsilon && t.surround(root) {
+				let hit_point = ray.at(root);
+
+				let mut record = HitRecord::new(hit_point, root, self.material.as_ref());
+				record.set_face_normal(ray, &self.normal(&(hit_point - self.center)));
+
+				return Some(record);
+			}
+
+			root += f64::max(distance, self.epsilon) / length;
+		}
+
+		None
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		self.bbox
+	}
+}
+
+impl SdfShape {
+	pub fn new(sdf: Sdf, center: Point3, material: Arc<dyn Material>) -> SdfShape {
+		let bbox = sdf.bounding_box();
+
+		SdfShape {
+			bbox: Aabb::from_points(bbox.min() + center, bbox.max() + center).pad(),
+			sdf,
+			center,
+			epsilon: 1e-4,
+			max_steps: 256,
+			material
+		}
+	}
+
+	pub fn epsilon(mut self, epsilon: f64) -> SdfShape {
+		self.epsilon = epsilon;
+		self
+	}
+
+	pub fn max_steps(mut self, max_steps: u32) -> SdfShape {
+		self.max_steps = max_steps;
+		self
+	}
+
+	// Central differences on the distance field
+	fn normal(&self, p: &Point3) -> Vec3 {
+		let h = self.epsilon;
+		let dx = Vec3::new(h, 0., 0.);
+		let dy = Vec3::new(0., h, 0.);
+		let dz = Vec3::new(0., 0., h);
+
+		Vec3::new(
+			self.sdf.distance(&(*p + dx)) - self.sdf.distance(&(*p - dx)),
+			self.sdf.distance(&(*p + dy)) - self.sdf.distance(&(*p - dy)),
+			self.sdf.distance(&(*p + dz)) - self.sdf.distance(&(*p - dz))
+		).unit()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::sdf::Sdf;
+	use crate::sdf_shape::SdfShape;
+	use crate::hittable::Hittable;
+	use crate::interval::Interval;
+	use crate::lambertian::Lambertian;
+	use crate::color::Color;
+	use crate::ray::Ray;
+	use crate::vec3::{Vec3, Point3};
+
+	fn shape(sdf: Sdf) -> SdfShape {
+		SdfShape::new(sdf, Point3::new(0., 0., 0.), Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))))
+	}
+
+	fn assert_front_hit(shape: &SdfShape, ray: &Ray, t: f64, normal: Vec3) {
+		let record = shape.hit(ray, &Interval::new(0.001, f64::MAX)).unwrap();
+
+		assert!(f64::abs(record.t - t) < 1e-3, "{} != {}", record.t, t);
+		assert!(record.front_face);
+		assert!((record.normal - normal).length() < 1e-3, "{:?} != {:?}", record.normal, normal);
+	}
+
+	#[test]
+	fn front_faces() {
+		let ray = Ray::new(Point3::new(0.1, 0.2, 5.), Vec3::new(0., 0., -1.));
+		let normal = Vec3::new(0., 0., 1.);
+
+		// Box-like shapes have their surface on the bounding box
+		assert_front_hit(&shape(Sdf::cuboid(Vec3::new(1., 1., 1.))), &ray, 4., normal);
+		assert_front_hit(&shape(Sdf::round_box(Vec3::new(1., 1., 1.), 0.1)), &ray, 4., normal);
+		assert_front_hit(&shape(Sdf::sphere(1.)), &Ray::new(Point3::new(0., 0., 5.), Vec3::new(0., 0., -2.)), 2., normal);
+		assert_front_hit(&shape(Sdf::torus(1., 0.25)), &Ray::new(Point3::new(1., 5., 0.), Vec3::new(0., -1., 0.)), 4.75, Vec3::new(0., 1., 0.));
+	}
+
+	#[test]
+	fn rays_leaving_the_surface() {
+		let cuboid = shape(Sdf::cuboid(Vec3::new(1., 1., 1.)));
+		let t = Interval::new(0.001, f64::MAX);
+
+		// Reflected off the front face
+		assert!(cuboid.hit(&Ray::new(Point3::new(0.1, 0.2, 1.), Vec3::new(0., 0., 1.)), &t).is_none());
+
+		// Refracted through it, hitting the back face from inside
+		let record = cuboid.hit(&Ray::new(Point3::new(0.1, 0.2, 1.), Vec3::new(0., 0., -1.)), &t).unwrap();
+		assert!(f64::abs(record.t - 2.) < 1e-3);
+		assert!(!record.front_face);
+	}
+}
\ No newline at end of file
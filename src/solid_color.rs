@@ -0,0 +1,21 @@
+use crate::texture::Texture;
+use crate::color::Color;
+use crate::vec3::Point3;
+
+pub struct SolidColor {
+	albedo: Color
+}
+
+impl Texture for SolidColor {
+	fn value(&self, _: f64, _: f64, _: &Point3) -> Color {
+		self.albedo
+	}
+}
+
+impl SolidColor {
+	pub fn new(albedo: Color) -> SolidColor {
+		SolidColor {
+			albedo
+		}
+	}
+}
\ No newline at end of file
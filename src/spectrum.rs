@@ -0,0 +1,134 @@
+use std::sync::OnceLock;
+
+use crate::color::Color;
+use crate::vec3::Vec3;
+use crate::cie::{self, LAMBDA_MIN, LAMBDA_MAX};
+
+// Wavelengths standing for the red, green and blue channels in RGB mode
+pub const RGB_WAVELENGTHS: [f64; 3] = [650., 550., 450.];
+
+// Wavelengths in nanometers carried by a path in spectral mode, one per colour channel
+#[derive(Clone, Copy, Debug)]
+pub struct Wavelengths {
+	lambdas: [f64; 3],
+	single: bool
+}
+
+impl Wavelengths {
+	// Hero wavelength plus two companions rotated by a third of the visible range (Wilkie et al. 2014)
+	pub fn sample(u: f64) -> Wavelengths {
+		let range = LAMBDA_MAX - LAMBDA_MIN;
+		let offset = u * range;
+
+		Wavelengths {
+			lambdas: [0., 1., 2.].map(|i| LAMBDA_MIN + (offset + i * range / 3.).rem_euclid(range)),
+			single: false
+		}
+	}
+
+	pub fn hero(&self) -> f64 {
+		self.lambdas[0]
+	}
+
+	pub fn lambda(&self, i: usize) -> f64 {
+		self.lambdas[i]
+	}
+
+	// Only the hero wavelength survives a wavelength dependent direction, e.g. dispersion
+	pub fn collapse(&self) -> Wavelengths {
+		Wavelengths {
+			lambdas: [self.hero(); 3],
+			single: true
+		}
+	}
+
+	pub fn is_single(&self) -> bool {
+		self.single
+	}
+}
+
+// Smooth blue, green and red bands summing to one, so grey values uplift to flat spectra
+pub fn value(rgb: &Color, lambda: f64) -> f64 {
+	let red = smoothstep(560., 615., lambda);
+	let blue = 1. - smoothstep(450., 525., lambda);
+	let green = 1. - red - blue;
+
+	rgb.x() * red + rgb.y() * green + rgb.z() * blue
+}
+
+pub fn uplift(rgb: &Color, wavelengths: &Wavelengths) -> Color {
+	Color::new(
+		value(rgb, wavelengths.lambda(0)),
+		value(rgb, wavelengths.lambda(1)),
+		value(rgb, wavelengths.lambda(2))
+	)
+}
+
+// Monte Carlo estimate of the XYZ integral, white balanced so a flat spectrum gives (1, 1, 1)
+pub fn to_rgb(radiance: &Color, wavelengths: &Wavelengths) -> Color {
+	let pdf = 1. / (LAMBDA_MAX - LAMBDA_MIN);
+	let xyz = (radiance.x() * cie::color_matching(wavelengths.lambda(0))
+		+ radiance.y() * cie::color_matching(wavelengths.lambda(1))
+		+ radiance.z() * cie::color_matching(wavelengths.lambda(2))) / (3. * pdf);
+
+	let rgb = cie::xyz_to_rgb(&xyz);
+	let white = white();
+
+	Color::new(rgb.x() / white.x(), rgb.y() / white.y(), rgb.z() / white.z())
+}
+
+fn white() -> Color {
+	static WHITE: OnceLock<Color> = OnceLock::new();
+
+	*WHITE.get_or_init(|| {
+		let xyz = (LAMBDA_MIN as i32..=LAMBDA_MAX as i32)
+			.map(|lambda| cie::color_matching(f64::from(lambda)))
+			.fold(Vec3::new(0., 0., 0.), |sum, xyz| sum + xyz);
+
+		cie::xyz_to_rgb(&xyz)
+	})
+}
+
+fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+	let t = f64::clamp((x - edge0) / (edge1 - edge0), 0., 1.);
+
+	t * t * (3. - 2. * t)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::spectrum::{self, Wavelengths};
+	use crate::color::Color;
+
+	fn integrate(rgb: &Color) -> Color {
+		let samples = 400;
+		(0..samples)
+			.map(|i| {
+				let wavelengths = Wavelengths::sample((i as f64 + 0.5) / samples as f64);
+				spectrum::to_rgb(&spectrum::uplift(rgb, &wavelengths), &wavelengths)
+			})
+			.fold(Color::new(0., 0., 0.), |sum, rgb| sum + rgb) / samples as f64
+	}
+
+	#[test]
+	fn grey_round_trip() {
+		let rgb = integrate(&Color::new(0.5, 0.5, 0.5));
+
+		assert!((rgb - Color::new(0.5, 0.5, 0.5)).length() < 1e-3, "{:?}", rgb);
+	}
+
+	#[test]
+	fn primaries_round_trip() {
+		for rgb in [Color::new(1., 0., 0.), Color::new(0., 1., 0.), Color::new(0., 0., 1.)] {
+			assert!((integrate(&rgb) - rgb).length() < 0.05, "{:?}", integrate(&rgb));
+		}
+	}
+
+	#[test]
+	fn collapse_keeps_hero() {
+		let wavelengths = Wavelengths::sample(0.25).collapse();
+
+		assert!(wavelengths.is_single());
+		assert_eq!(wavelengths.lambda(2), wavelengths.hero());
+	}
+}
\ No newline at end of file
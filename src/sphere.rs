@@ -1,19 +1,27 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
 use crate::interval::Interval;
 use crate::vec3::{Vec3, Point3};
 use crate::hittable::Hittable;
 use crate::hit_record::HitRecord;
 use crate::ray::Ray;
 use crate::material::Material;
+use crate::aabb::Aabb;
 
 pub struct Sphere {
 	center: Point3,
+	motion: Vec3,
+	// Start and end times of the motion
+	motion_times: (f64, f64),
 	radius: f64,
-	material: Box<dyn Material>
+	material: Arc<dyn Material>
 }
 
 impl Hittable for Sphere {
-	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord> {
-		let oc = ray.origin() - self.center;
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let center = self.center(ray.time());
+		let oc = ray.origin() - center;
 
 		let a = ray.direction().squared_length();
 		let half_b = oc.dot(ray.direction());
@@ -33,22 +41,100 @@ impl Hittable for Sphere {
 			}
 		}
 
-		let hit_point = ray.at(root);
-		let outward_normal: Vec3 = (hit_point - self.center) / self.radius;
-		
-		let mut record = HitRecord::new(hit_point, root, self.material.as_ref());
-		record.set_face_normal(ray, &outward_normal);
+		Some(self.record(ray, &center, root))
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		let radius = Vec3::new(self.radius, self.radius, self.radius);
+		let start = Aabb::from_points(self.center - radius, self.center + radius);
+		let end = Aabb::from_points(self.center + self.motion - radius, self.center + self.motion + radius);
+
+		Aabb::surrounding(&start, &end)
+	}
+
+	fn spans(&self, ray: &Ray) -> Vec<(HitRecord<'_>, HitRecord<'_>)> {
+		let center = self.center(ray.time());
+		let oc = ray.origin() - center;
+
+		let a = ray.direction().squared_length();
+		let half_b = oc.dot(ray.direction());
+		let c = oc.squared_length() - self.radius * self.radius;
+
+		let discriminant = half_b * half_b - a * c;
+		if discriminant <= 0. {
+			return Vec::new();
+		}
 
-		Some(record)
+		let sqrt_discr = f64::sqrt(discriminant);
+		let enter = self.record(ray, &center, (-half_b - sqrt_discr) / a);
+		let exit = self.record(ray, &center, (-half_b + sqrt_discr) / a);
+
+		vec![(enter, exit)]
 	}
 }
 
 impl Sphere {
-	pub fn new(center: Vec3, radius: f64, material: Box<dyn Material>) -> Sphere {
+	pub fn new(center: Vec3, radius: f64, material: Arc<dyn Material>) -> Sphere {
 		Sphere {
 			center,
+			motion: Vec3::new(0., 0., 0.),
+			motion_times: (0., 1.),
 			radius,
 			material
 		}
 	}
+
+	// Sphere travelling in a straight line from `start` at time 0 to `end` at time 1, it stays at
+	// either end outside of that interval. Times are in seconds, as the frames of `Camera::render_frames`
+	pub fn moving(start: Point3, end: Point3, radius: f64, material: Arc<dyn Material>) -> Sphere {
+		Sphere {
+			center: start,
+			motion: end - start,
+			motion_times: (0., 1.),
+			radius,
+			material
+		}
+	}
+
+	// Moves from `start_time` to `end_time` instead
+	pub fn during(mut self, start_time: f64, end_time: f64) -> Sphere {
+		self.motion_times = (start_time, end_time);
+		self
+	}
+
+	fn center(&self, time: f64) -> Point3 {
+		let (start_time, end_time) = self.motion_times;
+		let t = if end_time > start_time { (time - start_time) / (end_time - start_time) } else if time < start_time { 0. } else { 1. };
+
+		self.center + f64::clamp(t, 0., 1.) * self.motion
+	}
+
+	fn record(&self, ray: &Ray, center: &Point3, root: f64) -> HitRecord<'_> {
+		let hit_point = ray.at(root);
+		let outward_normal: Vec3 = (hit_point - *center) / self.radius;
+		
+		let mut record = HitRecord::new(hit_point, root, self.material.as_ref());
+		record.set_face_normal(ray, &outward_normal);
+		(record.u, record.v) = Sphere::uv(&outward_normal);
+		(record.dpdu, record.dpdv) = self.derivatives(&outward_normal);
+
+		record
+	}
+
+	// Derivatives of the `uv` parameterisation, the sine of the polar angle is clamped at the poles
+	fn derivatives(&self, n: &Vec3) -> (Vec3, Vec3) {
+		let sin_theta = f64::max(f64::sqrt(n.x() * n.x() + n.z() * n.z()), 1e-8);
+		let dpdu = 2. * PI * self.radius * Vec3::new(n.z(), 0., -n.x());
+		let dpdv = PI * self.radius * Vec3::new(-n.y() * n.x() / sin_theta, sin_theta, -n.y() * n.z() / sin_theta);
+
+		(dpdu, dpdv)
+	}
+
+	// u is the angle around the y axis starting at -x, v goes from the bottom pole (y = -1) to the top one
+	fn uv(p: &Point3) -> (f64, f64) {
+		let theta = f64::acos(-p.y());
+		let phi = f64::atan2(-p.z(), p.x()) + PI;
+
+		(phi / (2. * PI), theta / PI)
+	}
 }
\ No newline at end of file
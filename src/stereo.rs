@@ -0,0 +1,47 @@
+// How the two eyes are packed in the output image, the left eye comes first
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StereoLayout {
+	#[default]
+	SideBySide,
+	TopBottom
+}
+
+// Two eye rig around the camera position. Perspective projections use parallel off-axis frusta that meet at
+// the convergence distance, orthographic ones shear the parallel rays of each eye so they meet there too and
+// the disparity grows with the distance to that plane, panoramic ones use omni-directional stereo (Peleg et al. 2001) where every ray is
+// offset across its own horizontal direction and toed in towards the convergence distance
+#[derive(Clone, Copy, Debug)]
+pub struct Stereo {
+	pub ipd: f64,
+	pub convergence: f64,
+	pub layout: StereoLayout
+}
+
+impl Stereo {
+	// Interpupillary and zero parallax distances are in scene units, the latter must be positive
+	pub fn new(ipd: f64, convergence: f64) -> Stereo {
+		assert!(convergence > 0., "stereo convergence distance must be positive");
+
+		Stereo {
+			ipd,
+			convergence,
+			layout: StereoLayout::SideBySide
+		}
+	}
+
+	pub fn layout(mut self, layout: StereoLayout) -> Stereo {
+		self.layout = layout;
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::stereo::Stereo;
+
+	#[test]
+	#[should_panic]
+	fn zero_convergence() {
+		Stereo::new(0.064, 0.);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::dielectric::Dielectric;
+
+// Translucent material such as marble, wax or skin: a dielectric boundary around a scattering
+// interior that light random walks through until it leaves the closed shape
+pub struct Subsurface {
+	boundary: Dielectric,
+	scattering: Color
+}
+
+impl Material for Subsurface {
+	fn scatter(&self, ray: &Ray, rec: &HitRecord) -> Option<(Ray, Color)> {
+		self.boundary.scatter(ray, rec)
+	}
+}
+
+impl Subsurface {
+	// Single scattering albedo and mean free path per channel, in scene units
+	pub fn new(albedo: Color, mean_free_path: Color, ior: f64) -> Subsurface {
+		let extinction = Color::new(
+			1. / f64::max(mean_free_path.x(), 1e-6),
+			1. / f64::max(mean_free_path.y(), 1e-6),
+			1. / f64::max(mean_free_path.z(), 1e-6)
+		);
+
+		Subsurface::from_coefficients(extinction * (Color::new(1., 1., 1.) - albedo), extinction * albedo, ior)
+	}
+
+	pub fn from_coefficients(absorption: Color, scattering: Color, ior: f64) -> Subsurface {
+		Subsurface {
+			boundary: Dielectric::new(ior).absorption(absorption).scattering(scattering, 0.),
+			scattering
+		}
+	}
+
+	// Henyey-Greenstein asymmetry of the interior, skin is strongly forward scattering
+	pub fn anisotropy(mut self, g: f64) -> Subsurface {
+		self.boundary = self.boundary.scattering(self.scattering, g);
+		self
+	}
+
+	pub fn roughness(mut self, roughness: f64) -> Subsurface {
+		self.boundary = self.boundary.roughness(roughness);
+		self
+	}
+}
\ No newline at end of file
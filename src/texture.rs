@@ -0,0 +1,6 @@
+use crate::color::Color;
+use crate::vec3::Point3;
+
+pub trait Texture: Send + Sync {
+	fn value(&self, u: f64, v: f64, p: &Point3) -> Color;
+}
\ No newline at end of file
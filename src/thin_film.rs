@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::ray::Ray;
+use crate::hit_record::HitRecord;
+use crate::color::Color;
+use crate::texture::Texture;
+use crate::solid_color::SolidColor;
+use crate::spectrum::{self, RGB_WAVELENGTHS};
+use crate::fresnel;
+
+// Iridescent layer on top of a dielectric or a conductor, thickness in nanometers read from
+// the first channel of its texture
+pub struct ThinFilm {
+	thickness: Arc<dyn Texture>,
+	ior: f64
+}
+
+impl ThinFilm {
+	pub fn new(thickness: f64, ior: f64) -> ThinFilm {
+		ThinFilm::from_texture(Arc::new(SolidColor::new(Color::new(thickness, thickness, thickness))), ior)
+	}
+
+	pub fn from_texture(thickness: Arc<dyn Texture>, ior: f64) -> ThinFilm {
+		ThinFilm {
+			thickness,
+			ior
+		}
+	}
+
+	// Reflectance over a substrate eta + i k seen from a medium of IOR `incident_ior`. RGB paths get one
+	// wavelength per channel, spectral paths the exact value at their hero wavelength, the caller
+	// terminating the companions
+	pub fn reflectance(&self, ray: &Ray, rec: &HitRecord, cos_theta_i: f64, incident_ior: f64, eta: &Color, k: &Color) -> Color {
+		let thickness = f64::max(self.thickness.value(rec.u, rec.v, &rec.point).x(), 0.);
+		let reflectance = |lambda: f64, eta: f64, k: f64| {
+			fresnel::thin_film(cos_theta_i, lambda, thickness, self.ior / incident_ior, eta / incident_ior, k / incident_ior)
+		};
+
+		if let Some(wavelengths) = ray.wavelengths() {
+			let lambda = wavelengths.hero();
+			let r = reflectance(lambda, spectrum::value(eta, lambda), spectrum::value(k, lambda));
+			return Color::new(r, r, r);
+		}
+
+		Color::new(
+			reflectance(RGB_WAVELENGTHS[0], eta.x(), k.x()),
+			reflectance(RGB_WAVELENGTHS[1], eta.y(), k.y()),
+			reflectance(RGB_WAVELENGTHS[2], eta.z(), k.z())
+		)
+	}
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use std::f64::consts::PI;
+
+use crate::interval::Interval;
+use crate::vec3::{Vec3, Point3};
+use crate::hittable::Hittable;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::aabb::Aabb;
+use crate::polynomial::solve_quartic;
+
+// Torus lying in the xz plane around `center`, u follows the ring and v goes around the tube
+pub struct Torus {
+	center: Point3,
+	major_radius: f64,
+	minor_radius: f64,
+	phi_max: f64,
+	material: Arc<dyn Material>
+}
+
+impl Hittable for Torus {
+	fn hit(&self, ray: &Ray, t: &Interval) -> Option<HitRecord<'_>> {
+		let length = ray.direction().length();
+		let d = ray.direction() / length;
+
+		// The quartic loses precision far from the torus, so solve it from the closest point of the bounding sphere
+		let to_center = self.center - ray.origin();
+		let t_offset = f64::max(to_center.dot(d) - (self.major_radius + self.minor_radius), 0.);
+		let o = ray.origin() + t_offset * d - self.center;
+
+		let r2 = self.major_radius * self.major_radius;
+		let g = 4. * r2 * (d.x() * d.x() + d.z() * d.z());
+		let h = 8. * r2 * (o.x() * d.x() + o.z() * d.z());
+		let i = 4. * r2 * (o.x() * o.x() + o.z() * o.z());
+		let k = 2. * o.dot(d);
+		let l = o.squared_length() + r2 - self.minor_radius * self.minor_radius;
+
+		let roots = solve_quartic(1., 2. * k, 2. * l + k * k - g, 2. * k * l - h, l * l - i);
+		for root in roots {
+			let ray_t = (root + t_offset) / length;
+			if !t.surround(ray_t) {
+				continue;
+			}
+
+			let p = o + root * d;
+			let phi = f64::atan2(p.z(), p.x()).rem_euclid(2. * PI);
+			if phi > self.phi_max {
+				continue;
+			}
+
+			let s = p.squared_length() - r2 - self.minor_radius * self.minor_radius;
+			let outward_normal = Vec3::new(p.x() * s, p.y() * (s + 2. * r2), p.z() * s).unit();
+
+			let ring_distance = f64::sqrt(p.x() * p.x() + p.z() * p.z()) - self.major_radius;
+			let theta = f64::atan2(p.y(), ring_distance).rem_euclid(2. * PI);
+
+			let mut record = HitRecord::new(ray.at(ray_t), ray_t, self.material.as_ref());
+			record.set_face_normal(ray, &outward_normal);
+			record.u = phi / self.phi_max;
+			record.v = theta / (2. * PI);
+			record.dpdu = self.phi_max * Vec3::new(-p.z(), 0., p.x());
+			record.dpdv = 2. * PI * self.minor_radius * Vec3::new(
+				-f64::sin(theta) * f64::cos(phi),
+				f64::cos(theta),
+				-f64::sin(theta) * f64::sin(phi)
+			);
+
+			return Some(record);
+		}
+
+		None
+	}
+
+	fn bounding_box(&self) -> Aabb {
+		let extent = self.major_radius + self.minor_radius;
+		let extent = Vec3::new(extent, self.minor_radius, extent);
+
+		Aabb::from_points(self.center - extent, self.center + extent)
+	}
+}
+
+impl Torus {
+	pub fn new(center: Point3, major_radius: f64, minor_radius: f64, material: Arc<dyn Material>) -> Torus {
+		Torus {
+			center,
+			major_radius,
+			minor_radius,
+			phi_max: 2. * PI,
+			material
+		}
+	}
+
+	pub fn phi_max(mut self, degrees: f64) -> Torus {
+		self.phi_max = f64::clamp(degrees, 0., 360.).to_radians();
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::torus::Torus;
+	use crate::hit_assertions::{material, hit, assert_hit};
+	use crate::vec3::{Vec3, Point3};
+
+	#[test]
+	fn hit_tube() {
+		let torus = Torus::new(Point3::new(0., 0., 0.), 2., 0.5, material());
+
+		assert_hit(hit(&torus, Point3::new(-5., 0., 0.), Vec3::new(1., 0., 0.)), 2.5, Vec3::new(-1., 0., 0.), true);
+		assert_hit(hit(&torus, Point3::new(2., 5., 0.), Vec3::new(0., -1., 0.)), 4.5, Vec3::new(0., 1., 0.), true);
+		// Through the hole
+		assert!(hit(&torus, Point3::new(0., 5., 0.), Vec3::new(0., -1., 0.)).is_none());
+	}
+
+	#[test]
+	fn partial_sweep() {
+		let quarter = Torus::new(Point3::new(0., 0., 0.), 2., 0.5, material()).phi_max(90.);
+
+		// Only the tube crossing on +x is within the sweep
+		assert_hit(hit(&quarter, Point3::new(-5., 0., 0.), Vec3::new(1., 0., 0.)), 6.5, Vec3::new(-1., 0., 0.), true);
+	}
+}
\ No newline at end of file
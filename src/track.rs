@@ -0,0 +1,191 @@
+use crate::vec3::Vec3;
+
+// How a track moves between two keys
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+	Linear,
+	// Passes through every key with tangents from its neighbours
+	CatmullRom,
+	// Cubic Bezier through each key's handles, handles left on the key ease in and out of it
+	Bezier
+}
+
+// Values a track can animate, a blend is an affine combination (weights summing to one) of key values
+pub trait Keyable: Copy {
+	fn blend(values: &[Self; 4], weights: &[f64; 4]) -> Self;
+}
+
+impl Keyable for f64 {
+	fn blend(values: &[f64; 4], weights: &[f64; 4]) -> f64 {
+		values.iter().zip(weights).map(|(value, weight)| value * weight).sum()
+	}
+}
+
+impl Keyable for Vec3 {
+	fn blend(values: &[Vec3; 4], weights: &[f64; 4]) -> Vec3 {
+		values.iter().zip(weights).fold(Vec3::new(0., 0., 0.), |sum, (value, weight)| sum + *value * *weight)
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Key<T> {
+	time: f64,
+	value: T,
+	in_handle: T,
+	out_handle: T
+}
+
+// Value keyed at given times, it holds the first and last key outside of their range
+#[derive(Clone, Debug)]
+pub struct Track<T> {
+	keys: Vec<Key<T>>,
+	interpolation: Interpolation
+}
+
+impl<T: Copy> Track<T> {
+	pub fn new(interpolation: Interpolation) -> Track<T> {
+		Track {
+			keys: Vec::new(),
+			interpolation
+		}
+	}
+
+	// Track holding a single value
+	pub fn constant(value: T) -> Track<T> {
+		Track::new(Interpolation::Linear).key(0., value)
+	}
+
+	pub fn key(self, time: f64, value: T) -> Track<T> {
+		self.bezier_key(time, value, value, value)
+	}
+
+	// Key with the Bezier control points used before and after it, ignored by the other interpolations
+	pub fn bezier_key(mut self, time: f64, value: T, in_handle: T, out_handle: T) -> Track<T> {
+		let index = self.keys.partition_point(|key| key.time <= time);
+		self.keys.insert(index, Key {
+			time,
+			value,
+			in_handle,
+			out_handle
+		});
+
+		self
+	}
+
+	// Times of the first and last keys
+	pub fn range(&self) -> (f64, f64) {
+		match (self.keys.first(), self.keys.last()) {
+			(Some(first), Some(last)) => (first.time, last.time),
+			_ => (0., 0.)
+		}
+	}
+
+	pub fn values(&self) -> impl Iterator<Item = (f64, T)> + '_ {
+		self.keys.iter().map(|key| (key.time, key.value))
+	}
+
+	// Same keys and interpolation with every value converted, Bezier handles included
+	pub fn map<U: Copy, F: Fn(&T) -> U>(&self, f: F) -> Track<U> {
+		Track {
+			keys: self.keys.iter().map(|key| Key {
+				time: key.time,
+				value: f(&key.value),
+				in_handle: f(&key.in_handle),
+				out_handle: f(&key.out_handle)
+			}).collect(),
+			interpolation: self.interpolation
+		}
+	}
+
+	// Four control values and their weights at `time`
+	fn controls(&self, time: f64) -> Option<([T; 4], [f64; 4])> {
+		let (first, last) = (self.keys.first()?, self.keys.last()?);
+		if time <= first.time {
+			return Some(([first.value; 4], [1., 0., 0., 0.]));
+		}
+		if time >= last.time {
+			return Some(([last.value; 4], [1., 0., 0., 0.]));
+		}
+
+		let index = self.keys.partition_point(|key| key.time <= time);
+		let (a, b) = (&self.keys[index - 1], &self.keys[index]);
+		let t = (time - a.time) / (b.time - a.time);
+
+		let controls = match self.interpolation {
+			Interpolation::Linear => ([a.value, b.value, b.value, b.value], [1. - t, t, 0., 0.]),
+			Interpolation::CatmullRom => {
+				let before = if index >= 2 { &self.keys[index - 2] } else { a };
+				let after = if index + 1 < self.keys.len() { &self.keys[index + 1] } else { b };
+
+				let t2 = t * t;
+				let t3 = t2 * t;
+				let weights = [
+					0.5 * (-t3 + 2. * t2 - t),
+					0.5 * (3. * t3 - 5. * t2 + 2.),
+					0.5 * (-3. * t3 + 4. * t2 + t),
+					0.5 * (t3 - t2)
+				];
+
+				([before.value, a.value, b.value, after.value], weights)
+			},
+			Interpolation::Bezier => {
+				let s = 1. - t;
+				let weights = [s * s * s, 3. * s * s * t, 3. * s * t * t, t * t * t];
+
+				([a.value, a.out_handle, b.in_handle, b.value], weights)
+			}
+		};
+
+		Some(controls)
+	}
+}
+
+impl<T: Keyable> Track<T> {
+	pub fn at(&self, time: f64) -> Option<T> {
+		let (values, weights) = self.controls(time)?;
+
+		Some(T::blend(&values, &weights))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::track::{Track, Interpolation};
+
+	#[test]
+	fn passes_through_keys() {
+		for interpolation in [Interpolation::Linear, Interpolation::CatmullRom, Interpolation::Bezier] {
+			let track = Track::new(interpolation).key(0., 1.).key(1., 3.).key(3., -2.).key(4., 0.);
+
+			for (time, value) in track.values() {
+				assert!((track.at(time).unwrap() - value).abs() < 1e-12);
+			}
+			assert_eq!(track.at(-1.), Some(1.));
+			assert_eq!(track.at(10.), Some(0.));
+		}
+	}
+
+	#[test]
+	fn linear() {
+		let track = Track::new(Interpolation::Linear).key(2., 10.).key(0., 0.);
+
+		assert!((track.at(0.5).unwrap() - 2.5).abs() < 1e-12);
+	}
+
+	#[test]
+	fn catmull_rom_is_smooth() {
+		let track = Track::new(Interpolation::CatmullRom).key(0., 0.).key(1., 1.).key(2., 4.).key(3., 9.);
+
+		let slope = |time: f64| (track.at(time + 1e-6).unwrap() - track.at(time - 1e-6).unwrap()) / 2e-6;
+		assert!((slope(1.) - 2.).abs() < 1e-4);
+		assert!((slope(2.) - 4.).abs() < 1e-4);
+	}
+
+	#[test]
+	fn bezier_eases() {
+		let track = Track::new(Interpolation::Bezier).key(0., 0.).key(1., 1.);
+
+		assert!(track.at(0.01).unwrap() < 0.01);
+		assert!((track.at(0.5).unwrap() - 0.5).abs() < 1e-12);
+	}
+}
\ No newline at end of file
@@ -0,0 +1,323 @@
+use std::ops::Mul;
+
+use crate::vec3::{Vec3, Point3};
+use crate::ray::Ray;
+use crate::aabb::Aabb;
+
+type Matrix4 = [[f64; 4]; 4];
+pub type Matrix3 = [[f64; 3]; 3];
+
+const IDENTITY: Matrix4 = [
+	[1., 0., 0., 0.],
+	[0., 1., 0., 0.],
+	[0., 0., 1., 0.],
+	[0., 0., 0., 1.]
+];
+
+// Affine 4x4 transform, the inverse is kept alongside so rays can go back to object space for free
+#[derive(Clone, Copy, Debug)]
+pub struct Transform {
+	matrix: Matrix4,
+	inverse: Matrix4
+}
+
+impl Transform {
+	pub fn identity() -> Transform {
+		Transform {
+			matrix: IDENTITY,
+			inverse: IDENTITY
+		}
+	}
+
+	pub fn translate(offset: Vec3) -> Transform {
+		let mut matrix = IDENTITY;
+		let mut inverse = IDENTITY;
+		for i in 0..3 {
+			matrix[i][3] = offset.axis(i);
+			inverse[i][3] = -offset.axis(i);
+		}
+
+		Transform {
+			matrix,
+			inverse
+		}
+	}
+
+	pub fn scale(factors: Vec3) -> Transform {
+		let mut matrix = IDENTITY;
+		let mut inverse = IDENTITY;
+		for i in 0..3 {
+			matrix[i][i] = factors.axis(i);
+			inverse[i][i] = 1. / factors.axis(i);
+		}
+
+		Transform {
+			matrix,
+			inverse
+		}
+	}
+
+	pub fn rotate(axis: Vec3, degrees: f64) -> Transform {
+		let a = axis.unit();
+		let (sin, cos) = f64::sin_cos(degrees.to_radians());
+		let k = 1. - cos;
+
+		let matrix = [
+			[cos + a.x() * a.x() * k, a.x() * a.y() * k - a.z() * sin, a.x() * a.z() * k + a.y() * sin, 0.],
+			[a.y() * a.x() * k + a.z() * sin, cos + a.y() * a.y() * k, a.y() * a.z() * k - a.x() * sin, 0.],
+			[a.z() * a.x() * k - a.y() * sin, a.z() * a.y() * k + a.x() * sin, cos + a.z() * a.z() * k, 0.],
+			[0., 0., 0., 1.]
+		];
+
+		Transform {
+			matrix,
+			inverse: transpose(&matrix)
+		}
+	}
+
+	// Places an object at `from` with its -z axis pointing to `at`, same basis as the camera
+	pub fn look_at(from: Point3, at: Point3, up: Vec3) -> Transform {
+		let w = (from - at).unit();
+		let u = up.cross(w).unit();
+		let v = w.cross(u);
+
+		let rotation = [
+			[u.x(), v.x(), w.x(), 0.],
+			[u.y(), v.y(), w.y(), 0.],
+			[u.z(), v.z(), w.z(), 0.],
+			[0., 0., 0., 1.]
+		];
+		let rotation = Transform {
+			matrix: rotation,
+			inverse: transpose(&rotation)
+		};
+
+		Transform::translate(from) * rotation
+	}
+
+	// Arbitrary invertible linear map, e.g. a rotation and scale recombined after interpolation
+	pub fn linear(m: Matrix3) -> Transform {
+		let cofactor = |i: usize, j: usize| {
+			let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+			let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+			m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+		};
+		let determinant = (0..3).map(|j| m[0][j] * cofactor(0, j)).sum::<f64>();
+
+		let mut matrix = IDENTITY;
+		let mut inverse = IDENTITY;
+		for i in 0..3 {
+			for j in 0..3 {
+				matrix[i][j] = m[i][j];
+				inverse[i][j] = cofactor(j, i) / determinant;
+			}
+		}
+
+		Transform {
+			matrix,
+			inverse
+		}
+	}
+
+	pub fn translation(&self) -> Vec3 {
+		Vec3::new(self.matrix[0][3], self.matrix[1][3], self.matrix[2][3])
+	}
+
+	// Upper 3x3 block, the transform without its translation
+	pub fn linear_part(&self) -> Matrix3 {
+		let mut result = [[0.; 3]; 3];
+		for (i, row) in result.iter_mut().enumerate() {
+			row.copy_from_slice(&self.matrix[i][..3]);
+		}
+
+		result
+	}
+
+	pub fn inverse(&self) -> Transform {
+		Transform {
+			matrix: self.inverse,
+			inverse: self.matrix
+		}
+	}
+
+	pub fn point(&self, p: &Point3) -> Point3 {
+		let m = &self.matrix;
+		let x = m[0][0] * p.x() + m[0][1] * p.y() + m[0][2] * p.z() + m[0][3];
+		let y = m[1][0] * p.x() + m[1][1] * p.y() + m[1][2] * p.z() + m[1][3];
+		let z = m[2][0] * p.x() + m[2][1] * p.y() + m[2][2] * p.z() + m[2][3];
+		let w = m[3][0] * p.x() + m[3][1] * p.y() + m[3][2] * p.z() + m[3][3];
+
+		if w == 1. { Point3::new(x, y, z) } else { Point3::new(x, y, z) / w }
+	}
+
+	pub fn vector(&self, v: &Vec3) -> Vec3 {
+		let m = &self.matrix;
+
+		Vec3::new(
+			m[0][0] * v.x() + m[0][1] * v.y() + m[0][2] * v.z(),
+			m[1][0] * v.x() + m[1][1] * v.y() + m[1][2] * v.z(),
+			m[2][0] * v.x() + m[2][1] * v.y() + m[2][2] * v.z()
+		)
+	}
+
+	// Normals go through the inverse transpose to stay perpendicular under non-uniform scaling
+	pub fn normal(&self, n: &Vec3) -> Vec3 {
+		let m = &self.inverse;
+
+		Vec3::new(
+			m[0][0] * n.x() + m[1][0] * n.y() + m[2][0] * n.z(),
+			m[0][1] * n.x() + m[1][1] * n.y() + m[2][1] * n.z(),
+			m[0][2] * n.x() + m[1][2] * n.y() + m[2][2] * n.z()
+		)
+	}
+
+	// The direction is not normalised so that t stays the same on both sides of the transform
+	pub fn ray(&self, ray: &Ray) -> Ray {
+		ray.spawn(self.point(&ray.origin()), self.vector(&ray.direction()))
+	}
+
+	pub fn bounding_box(&self, bbox: &Aabb) -> Aabb {
+		// Corners at the ends of the number line overflow into infinities that sum to NaN, so unbounded
+		// objects like planes stay unbounded
+		let axes = [bbox.x, bbox.y, bbox.z];
+		if axes.iter().any(|axis| axis.min > axis.max) {
+			return Aabb::empty();
+		}
+		if axes.iter().any(|axis| axis.min <= f64::MIN || axis.max >= f64::MAX) {
+			return Aabb::universe();
+		}
+
+		let mut result = Aabb::empty();
+		for i in 0..8 {
+			let corner = Point3::new(
+				if i & 1 == 0 { bbox.x.min } else { bbox.x.max },
+				if i & 2 == 0 { bbox.y.min } else { bbox.y.max },
+				if i & 4 == 0 { bbox.z.min } else { bbox.z.max }
+			);
+			let p = self.point(&corner);
+
+			result = Aabb::surrounding(&result, &Aabb::from_points(p, p));
+		}
+
+		result
+	}
+}
+
+impl Default for Transform {
+	fn default() -> Self {
+		Transform::identity()
+	}
+}
+
+// `a * b` applies `b` first, then `a`
+impl Mul for Transform {
+	type Output = Transform;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		Transform {
+			matrix: multiply(&self.matrix, &rhs.matrix),
+			inverse: multiply(&rhs.inverse, &self.inverse)
+		}
+	}
+}
+
+fn multiply(lhs: &Matrix4, rhs: &Matrix4) -> Matrix4 {
+	let mut result = [[0.; 4]; 4];
+	for (i, row) in result.iter_mut().enumerate() {
+		for (j, value) in row.iter_mut().enumerate() {
+			*value = (0..4).map(|k| lhs[i][k] * rhs[k][j]).sum();
+		}
+	}
+
+	result
+}
+
+fn transpose(m: &Matrix4) -> Matrix4 {
+	let mut result = [[0.; 4]; 4];
+	for (i, row) in result.iter_mut().enumerate() {
+		for (j, value) in row.iter_mut().enumerate() {
+			*value = m[j][i];
+		}
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::aabb::Aabb;
+	use crate::transform::Transform;
+	use crate::vec3::{Vec3, Point3};
+
+	fn assert_near(a: Vec3, b: Vec3) {
+		assert!((a - b).length() < 1e-9, "{:?} != {:?}", a, b);
+	}
+
+	#[test]
+	fn translate() {
+		let transform = Transform::translate(Vec3::new(1., 2., 3.));
+
+		assert_near(transform.point(&Point3::new(1., 1., 1.)), Point3::new(2., 3., 4.));
+		assert_near(transform.vector(&Vec3::new(1., 1., 1.)), Vec3::new(1., 1., 1.));
+	}
+
+	#[test]
+	fn rotate() {
+		let transform = Transform::rotate(Vec3::new(0., 1., 0.), 90.);
+
+		assert_near(transform.point(&Point3::new(1., 0., 0.)), Point3::new(0., 0., -1.));
+	}
+
+	#[test]
+	fn compose() {
+		let transform = Transform::translate(Vec3::new(1., 0., 0.)) * Transform::scale(Vec3::new(2., 2., 2.));
+
+		assert_near(transform.point(&Point3::new(1., 1., 1.)), Point3::new(3., 2., 2.));
+	}
+
+	#[test]
+	fn inverse() {
+		let transform = Transform::translate(Vec3::new(1., -2., 3.))
+			* Transform::rotate(Vec3::new(1., 1., 0.), 33.)
+			* Transform::scale(Vec3::new(2., 0.5, 3.));
+		let p = Point3::new(0.3, -4., 7.);
+
+		assert_near(transform.inverse().point(&transform.point(&p)), p);
+	}
+
+	#[test]
+	fn look_at() {
+		let transform = Transform::look_at(Point3::new(0., 0., 5.), Point3::new(0., 0., 0.), Vec3::new(0., 1., 0.));
+
+		assert_near(transform.point(&Point3::new(0., 0., -5.)), Point3::new(0., 0., 0.));
+	}
+
+	#[test]
+	fn linear() {
+		let transform = Transform::rotate(Vec3::new(1., 2., 3.), 40.) * Transform::scale(Vec3::new(2., 3., 0.5));
+		let linear = Transform::linear(transform.linear_part());
+		let p = Point3::new(1., -2., 0.5);
+
+		assert_near(linear.point(&p), transform.point(&p));
+		assert_near(linear.inverse().point(&linear.point(&p)), p);
+	}
+
+	#[test]
+	fn normal() {
+		let transform = Transform::scale(Vec3::new(1., 4., 1.));
+		let tangent = Vec3::new(1., -1., 0.);
+		let normal = Vec3::new(1., 1., 0.);
+
+		assert!(transform.vector(&tangent).dot(transform.normal(&normal)).abs() < 1e-9);
+	}
+
+	#[test]
+	fn unbounded_boxes() {
+		let transform = Transform::rotate(Vec3::new(1., 1., 0.), 30.) * Transform::scale(Vec3::new(2., 2., 2.));
+		let bbox = transform.bounding_box(&Aabb::universe());
+
+		assert!(bbox.x.min == f64::MIN && bbox.x.max == f64::MAX);
+		assert!(bbox.z.min == f64::MIN && bbox.z.max == f64::MAX);
+		assert!(transform.bounding_box(&Aabb::empty()).x.size() < 0.);
+	}
+}
\ No newline at end of file
@@ -9,7 +9,7 @@ pub struct Vec3 {
 pub type Point3 = Vec3;
 
 impl Vec3 {
-	pub fn new(v1: f64, v2: f64, v3: f64) -> Vec3 {
+	pub const fn new(v1: f64, v2: f64, v3: f64) -> Vec3 {
 		Vec3 {
 			values: [v1, v2, v3]
 		}
@@ -39,7 +39,7 @@ impl Vec3 {
 	pub fn random_in_unit_disk() -> Vec3 {
 		let mut rng = rand::thread_rng();
 		loop {
-			let vec = Vec3::new(rng.gen(), rng.gen(), 0.);
+			let vec = Vec3::new(rng.gen_range(-1. ..1.), rng.gen_range(-1. ..1.), 0.);
 			if vec.squared_length() < 1. {
 				return vec;
 			}
@@ -59,6 +59,18 @@ impl Vec3 {
 		on_unit_sphere
 	}
 
+	// Cosine weighted direction around +z
+	pub fn random_cosine_direction() -> Vec3 {
+		let mut rng = rand::thread_rng();
+		let r1: f64 = rng.gen();
+		let r2: f64 = rng.gen();
+
+		let phi = 2. * std::f64::consts::PI * r1;
+		let r = f64::sqrt(r2);
+
+		Vec3::new(f64::cos(phi) * r, f64::sin(phi) * r, f64::sqrt(1. - r2))
+	}
+
 	pub fn x(&self) -> f64 {
 		self.values[0]
 	} 
@@ -71,6 +83,10 @@ impl Vec3 {
 		self.values[2]
 	}
 
+	pub fn axis(&self, n: usize) -> f64 {
+		self.values[n]
+	}
+
 	pub fn cross(&self, rhs: Vec3) -> Vec3 {
 		Vec3::new(
 			self.y() * rhs.z() - self.z() * rhs.y(),
@@ -0,0 +1,159 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+
+use crate::vec3::Point3;
+
+// Dense scalar grid covering the unit cube, voxel values sit at the voxel centers.
+//
+// Raw format: nx, ny, nz as little-endian u32 followed by nx * ny * nz little-endian f32, x varying fastest
+pub struct VoxelGrid {
+	nx: usize,
+	ny: usize,
+	nz: usize,
+	values: Vec<f32>,
+	max_value: f64
+}
+
+impl VoxelGrid {
+	pub fn new(nx: usize, ny: usize, nz: usize, values: Vec<f32>) -> VoxelGrid {
+		assert!(nx > 0 && ny > 0 && nz > 0, "grid dimensions must not be zero");
+		assert_eq!(Some(values.len()), VoxelGrid::size(nx, ny, nz), "grid size does not match its dimensions");
+		let max_value = values.iter().fold(0., |max: f64, value| f64::max(max, f64::from(*value)));
+
+		VoxelGrid {
+			nx,
+			ny,
+			nz,
+			values,
+			max_value
+		}
+	}
+
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<VoxelGrid> {
+		VoxelGrid::read(BufReader::new(File::open(path)?))
+	}
+
+	pub fn read<R: Read>(mut reader: R) -> io::Result<VoxelGrid> {
+		let mut header = [0u8; 12];
+		reader.read_exact(&mut header)?;
+		let dimension = |i: usize| u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]) as usize;
+		let (nx, ny, nz) = (dimension(0), dimension(4), dimension(8));
+		if nx == 0 || ny == 0 || nz == 0 {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "grid dimensions must not be zero"));
+		}
+		let Some(length) = VoxelGrid::size(nx, ny, nz).and_then(|size| size.checked_mul(4)) else {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "grid dimensions are too large"));
+		};
+
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes)?;
+		if bytes.len() != length {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "grid data does not match its dimensions"));
+		}
+
+		let values = bytes.chunks_exact(4)
+			.map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+			.collect();
+
+		Ok(VoxelGrid::new(nx, ny, nz, values))
+	}
+
+	pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+		for dimension in [self.nx, self.ny, self.nz] {
+			writer.write_all(&(dimension as u32).to_le_bytes())?;
+		}
+		for value in self.values.iter() {
+			writer.write_all(&value.to_le_bytes())?;
+		}
+
+		Ok(())
+	}
+
+	pub fn max_value(&self) -> f64 {
+		self.max_value
+	}
+
+	// Trilinear interpolation at `p` in [0, 1]³, zero outside
+	pub fn lookup(&self, p: &Point3) -> f64 {
+		let unit = 0. ..=1.;
+		if !unit.contains(&p.x()) || !unit.contains(&p.y()) || !unit.contains(&p.z()) {
+			return 0.;
+		}
+
+		let gx = p.x() * self.nx as f64 - 0.5;
+		let gy = p.y() * self.ny as f64 - 0.5;
+		let gz = p.z() * self.nz as f64 - 0.5;
+		let (x0, y0, z0) = (f64::floor(gx), f64::floor(gy), f64::floor(gz));
+		let (fx, fy, fz) = (gx - x0, gy - y0, gz - z0);
+
+		let mut value = 0.;
+		for (dz, wz) in [(0, 1. - fz), (1, fz)] {
+			for (dy, wy) in [(0, 1. - fy), (1, fy)] {
+				for (dx, wx) in [(0, 1. - fx), (1, fx)] {
+					value += wx * wy * wz * self.voxel(x0 as i64 + dx, y0 as i64 + dy, z0 as i64 + dz);
+				}
+			}
+		}
+
+		value
+	}
+
+	// Number of voxels, None when it overflows
+	fn size(nx: usize, ny: usize, nz: usize) -> Option<usize> {
+		nx.checked_mul(ny)?.checked_mul(nz)
+	}
+
+	// Clamped to the border so the grid does not fade out over its outer half voxel
+	fn voxel(&self, x: i64, y: i64, z: i64) -> f64 {
+		let x = i64::clamp(x, 0, self.nx as i64 - 1) as usize;
+		let y = i64::clamp(y, 0, self.ny as i64 - 1) as usize;
+		let z = i64::clamp(z, 0, self.nz as i64 - 1) as usize;
+
+		f64::from(self.values[(z * self.ny + y) * self.nx + x])
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::voxel_grid::VoxelGrid;
+	use crate::vec3::Point3;
+
+	#[test]
+	fn lookup() {
+		let grid = VoxelGrid::new(2, 1, 1, vec![0., 1.]);
+
+		assert_eq!(grid.lookup(&Point3::new(0.25, 0.5, 0.5)), 0.);
+		assert_eq!(grid.lookup(&Point3::new(0.5, 0.5, 0.5)), 0.5);
+		assert_eq!(grid.lookup(&Point3::new(0.75, 0.5, 0.5)), 1.);
+		assert_eq!(grid.lookup(&Point3::new(1.5, 0.5, 0.5)), 0.);
+	}
+
+	#[test]
+	fn read_write() {
+		let grid = VoxelGrid::new(1, 2, 2, vec![0., 1., 2., 3.]);
+
+		let mut bytes = Vec::new();
+		grid.write(&mut bytes).unwrap();
+		let read = VoxelGrid::read(bytes.as_slice()).unwrap();
+
+		assert_eq!(read.values, grid.values);
+		assert_eq!(read.max_value(), 3.);
+		assert!(VoxelGrid::read(&bytes[..bytes.len() - 1]).is_err());
+	}
+
+	#[test]
+	fn rejects_bad_headers() {
+		let header = |nx: u32, ny: u32, nz: u32| [nx, ny, nz].iter().flat_map(|n| n.to_le_bytes()).collect::<Vec<u8>>();
+
+		assert!(VoxelGrid::read(header(0, 4, 4).as_slice()).is_err());
+		assert!(VoxelGrid::read(header(4, 0, 0).as_slice()).is_err());
+		assert!(VoxelGrid::read(header(u32::MAX, u32::MAX, u32::MAX).as_slice()).is_err());
+	}
+
+	#[test]
+	#[should_panic]
+	fn rejects_empty_grids() {
+		VoxelGrid::new(0, 1, 1, Vec::new());
+	}
+}
\ No newline at end of file